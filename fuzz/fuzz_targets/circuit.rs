@@ -0,0 +1,17 @@
+//! Day 7 circuits, one gate per line
+
+#![no_main]
+
+use aoc2015::day7::Circuit;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    // Any circuit that loads has to settle on a signal for every wire
+    if let Ok(mut circuit) = Circuit::load(&lines) {
+        for wire in circuit.wires().collect::<Vec<_>>() {
+            circuit.evaluate(wire);
+        }
+    }
+});
@@ -0,0 +1,17 @@
+//! Day 1 instructions, which should only ever be brackets
+
+#![no_main]
+
+use aoc2015::day1::{find_basement, find_floor};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|instructions: &str| {
+    let floor = find_floor(instructions);
+    let basement = find_basement(instructions);
+
+    // Getting all the way to the end means getting to the basement too,
+    // if we went there at all
+    if let (Ok(_), Err(err)) = (floor, basement) {
+        assert_eq!(err, aoc2015::day1::Error::NoBasement);
+    }
+});
@@ -0,0 +1,13 @@
+//! Day 6 light instructions, like `toggle 1,2 through 3,4`
+
+#![no_main]
+
+use aoc2015::day6::{Instruction, GRID_SIZE};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(instruction) = Instruction::parse(text) {
+        let region = instruction.region();
+        assert!(region.width() <= GRID_SIZE && region.height() <= GRID_SIZE);
+    }
+});
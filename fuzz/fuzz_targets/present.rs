@@ -0,0 +1,24 @@
+//! Day 2 present dimensions, like `2x3x4`, and the paper and ribbon
+//! worked out from them
+
+#![no_main]
+
+use aoc2015::day2::Present;
+use aoc2015::solutions::day;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(present) = text.parse::<Present>() {
+        // Whatever was read has to be written the same way
+        let again = format!("{}x{}x{}", present.l, present.w, present.h).parse::<Present>().unwrap();
+        assert_eq!((again.l, again.w, again.h), (present.l, present.w, present.h));
+
+        // The slack is the smallest of the six sides
+        assert!(u64::from(present.total_area()) >= 6 * u64::from(present.slack()));
+    }
+
+    // The totals have to come out as an answer or an error, never a panic
+    let day = day(2).unwrap();
+    let _ = (day.part1)(text);
+    let _ = (day.part2)(text);
+});
@@ -0,0 +1,351 @@
+//! Timing every day, and spotting when something has got slower
+//!
+//! Measurements are written as tab separated values, one line per stage
+//! of each day, with the columns in [`HEADER`], so they can be saved as a
+//! baseline and compared against later. Stages a day doesn't have are
+//! written with `-` for every timing.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::solutions::{Day, SolveError};
+
+/// The column headings of the machine readable output
+pub const HEADER: &str = "day\tstage\titerations\tmin_ns\tmedian_ns\tmean_ns";
+
+/// Which bit of a day is being timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+
+        f.pad(stage)
+    }
+}
+
+impl std::str::FromStr for Stage {
+    type Err = BenchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Stage::ALL
+            .into_iter()
+            .find(|stage| stage.to_string() == s)
+            .ok_or_else(|| BenchError { line: 0, text: s.to_string() })
+    }
+}
+
+/// How long to spend timing each stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Keep going until at least this long has been spent...
+    pub target: Duration,
+
+    /// ...or this many runs have been timed, whichever is first. Slow
+    /// stages are still run at least once
+    pub max_iterations: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { target: Duration::from_secs(1), max_iterations: 1000 }
+    }
+}
+
+/// How long a stage took over a number of runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let total = samples.iter().sum::<Duration>();
+
+        Self {
+            iterations: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+}
+
+/// The timings for one stage of a day. There aren't any for a stage the
+/// day doesn't have, like parsing for days that work straight on the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub timing: Option<Timing>,
+}
+
+impl fmt::Display for Measurement {
+    /// A line of tab separated values, in the same order as [`HEADER`]
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use aoc2015::bench::*;
+    /// let timing = Timing { iterations: 3, min: Duration::from_nanos(90), median: Duration::from_nanos(100), mean: Duration::from_nanos(110) };
+    /// assert_eq!(Measurement { day: 4, stage: Stage::Part1, timing: Some(timing) }.to_string(), "4\tpart1\t3\t90\t100\t110");
+    /// assert_eq!(Measurement { day: 4, stage: Stage::Parse, timing: None }.to_string(), "4\tparse\t-\t-\t-\t-");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.timing {
+            Some(timing) => write!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                self.day,
+                self.stage,
+                timing.iterations,
+                timing.min.as_nanos(),
+                timing.median.as_nanos(),
+                timing.mean.as_nanos(),
+            ),
+            None => write!(f, "{}\t{}\t-\t-\t-\t-", self.day, self.stage),
+        }
+    }
+}
+
+/// A stage that returned an error rather than an answer, so there's
+/// nothing worth timing
+#[derive(Debug)]
+pub struct StageFailed {
+    pub day: u8,
+    pub stage: Stage,
+    pub error: SolveError,
+}
+
+impl fmt::Display for StageFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} {} failed: {}", self.day, self.stage, self.error)
+    }
+}
+
+impl std::error::Error for StageFailed {}
+
+/// Time the parsing and both parts of a day on an input. Days without a
+/// parser still get a parse measurement, just without any timing
+///
+/// ```
+/// # use std::time::Duration;
+/// # use aoc2015::bench::*;
+/// # use aoc2015::solutions::day;
+/// let settings = Settings { target: Duration::from_millis(10), max_iterations: 5 };
+/// let measurements = bench(day(2).unwrap(), "2x3x4", &settings).unwrap();
+///
+/// assert_eq!(measurements.len(), 3);
+/// assert_eq!(measurements[1].stage, Stage::Part1);
+/// assert!(measurements.iter().flat_map(|m| m.timing).all(|t| t.iterations == 5 && t.min <= t.median));
+///
+/// let measurements = bench(day(1).unwrap(), "(()))", &settings).unwrap();
+/// assert_eq!(measurements.iter().map(|m| m.stage).collect::<Vec<_>>(), Stage::ALL);
+/// assert_eq!(measurements[0].timing, None);
+/// assert!(measurements[1].timing.is_some());
+///
+/// let failed = bench(day(2).unwrap(), "2x3", &settings).unwrap_err();
+/// assert_eq!(failed.stage, Stage::Parse);
+/// ```
+pub fn bench(day: &Day, input: &str, settings: &Settings) -> Result<Vec<Measurement>, StageFailed> {
+    Stage::ALL
+        .into_iter()
+        .map(|stage| {
+            let timing = match stage {
+                Stage::Parse => day.parse.map(|parse| time(input, settings, |input| parse(input).map(drop))),
+                Stage::Part1 => Some(time(input, settings, |input| (day.part1)(input).map(drop))),
+                Stage::Part2 => Some(time(input, settings, |input| (day.part2)(input).map(drop))),
+            };
+
+            let timing = timing.transpose().map_err(|error| StageFailed { day: day.day, stage, error })?;
+            Ok(Measurement { day: day.day, stage, timing })
+        })
+        .collect()
+}
+
+/// Run a stage over and over, until the settings say that's enough
+fn time(input: &str, settings: &Settings, run: impl Fn(&str) -> Result<(), SolveError>) -> Result<Timing, SolveError> {
+    let mut samples = Vec::new();
+    let started = Instant::now();
+    while samples.is_empty() || (samples.len() < settings.max_iterations && started.elapsed() < settings.target) {
+        let start = Instant::now();
+        let result = black_box(run(black_box(input)));
+        samples.push(start.elapsed());
+        result?;
+    }
+
+    Ok(Timing::from_samples(samples))
+}
+
+/// A line of a baseline that couldn't be understood
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchError {
+    /// The line the problem is on, counting from one
+    pub line: usize,
+
+    /// The text that couldn't be parsed
+    pub text: String,
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Couldn't understand line {} of the baseline: {}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+/// Previously saved median timings, to compare against. Stages that
+/// weren't timed are kept too, without a median
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, Stage), Option<Duration>>,
+}
+
+impl Baseline {
+    /// Load a baseline from saved measurements, skipping the header
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use aoc2015::bench::*;
+    /// let baseline = Baseline::parse("day\tstage\titerations\tmin_ns\tmedian_ns\tmean_ns\n4\tpart1\t3\t90\t100\t110\n4\tparse\t-\t-\t-\t-").unwrap();
+    /// assert_eq!(baseline.median(4, Stage::Part1), Some(Duration::from_nanos(100)));
+    /// assert_eq!(baseline.median(4, Stage::Part2), None);
+    /// assert_eq!(baseline.median(4, Stage::Parse), None);
+    /// assert!(baseline.contains(4, Stage::Parse));
+    ///
+    /// assert_eq!(Baseline::parse("4\tpart3\t3\t90\t100\t110").unwrap_err().line, 1);
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, BenchError> {
+        let mut medians = BTreeMap::new();
+        for (index, text) in text.lines().enumerate() {
+            let text = text.trim();
+            if text.is_empty() || text == HEADER {
+                continue;
+            }
+
+            let error = || BenchError { line: index + 1, text: text.to_string() };
+            let fields = text.split('\t').collect::<Vec<_>>();
+            let [day, stage, _, _, median, _] = fields[..] else {
+                return Err(error());
+            };
+
+            let day = day.parse().map_err(|_| error())?;
+            let stage = stage.parse().map_err(|_| error())?;
+            let median = match median {
+                "-" => None,
+                median => Some(Duration::from_nanos(median.parse().map_err(|_| error())?)),
+            };
+            medians.insert((day, stage), median);
+        }
+
+        Ok(Self { medians })
+    }
+
+    /// Whether the stage is in the baseline at all, timed or not
+    pub fn contains(&self, day: u8, stage: Stage) -> bool {
+        self.medians.contains_key(&(day, stage))
+    }
+
+    pub fn median(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.medians.get(&(day, stage)).copied().flatten()
+    }
+}
+
+impl FromIterator<Measurement> for Baseline {
+    fn from_iter<I: IntoIterator<Item = Measurement>>(iter: I) -> Self {
+        Self { medians: iter.into_iter().map(|m| ((m.day, m.stage), m.timing.map(|t| t.median))).collect() }
+    }
+}
+
+/// How a stage's median time compares with its baseline. Either side is
+/// missing if the stage wasn't timed then
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Option<Duration>,
+    pub current: Option<Duration>,
+
+    /// How much longer (or shorter, if negative) it took now, as a
+    /// fraction of the baseline, if it was timed both times
+    pub change: Option<f64>,
+}
+
+impl Comparison {
+    /// Whether it's slowed down by more than the allowed fraction
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change.is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compare measurements against a baseline. Stages that aren't in the
+/// baseline are left out
+///
+/// ```
+/// # use std::time::Duration;
+/// # use aoc2015::bench::*;
+/// let baseline = Baseline::parse("1\tparse\t-\t-\t-\t-\n1\tpart1\t1\t100\t100\t100").unwrap();
+/// let now = |stage, nanos: Option<u64>| Measurement {
+///     day: 1,
+///     stage,
+///     timing: nanos.map(Duration::from_nanos).map(|time| Timing { iterations: 1, min: time, median: time, mean: time }),
+/// };
+///
+/// let comparisons = compare(&[now(Stage::Parse, None), now(Stage::Part1, Some(150)), now(Stage::Part2, Some(5))], &baseline);
+/// assert_eq!(comparisons.len(), 2);
+/// assert_eq!(comparisons[0].change, None);
+/// assert!(!comparisons[0].is_regression(0.1));
+/// assert!(comparisons[1].is_regression(0.1));
+/// assert_eq!(comparisons[1].change, Some(0.5));
+/// ```
+pub fn compare(measurements: &[Measurement], baseline: &Baseline) -> Vec<Comparison> {
+    measurements
+        .iter()
+        .filter(|m| baseline.contains(m.day, m.stage))
+        .map(|m| {
+            let before = baseline.median(m.day, m.stage);
+            let now = m.timing.map(|t| t.median);
+            let change = before.zip(now).map(|(before, now)| {
+                (now.as_secs_f64() - before.as_secs_f64()) / before.as_secs_f64().max(f64::MIN_POSITIVE)
+            });
+            Comparison { day: m.day, stage: m.stage, baseline: before, current: now, change }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::DAYS;
+
+    #[test]
+    pub fn saved_measurements_are_a_baseline() {
+        let settings = Settings { target: Duration::ZERO, max_iterations: 1 };
+        let measurements = bench(&DAYS[0], "(()))", &settings).unwrap();
+        let saved = measurements.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n");
+
+        let baseline = Baseline::parse(&format!("{HEADER}\n{saved}\n")).unwrap();
+        assert_eq!(baseline, measurements.iter().copied().collect());
+        assert!(compare(&measurements, &baseline).iter().all(|c| c.change.unwrap_or_default() == 0.0));
+        assert_eq!(compare(&measurements, &baseline)[0].change, None);
+    }
+}
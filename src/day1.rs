@@ -1,11 +1,35 @@
 //! Day 1 - Not Quite Lisp
 
+use core::fmt;
+
+/// Instructions that couldn't be followed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Something other than a bracket, and where it was (counting from one)
+    Unknown { position: usize, found: char },
+
+    /// The instructions never went down to the basement
+    NoBasement,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unknown { position, found } => write!(f, "Don't know what to do with a {found:?} at {position}"),
+            Error::NoBasement => write!(f, "We never went down to the basement"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 // Maps a set of instructions to a set of matching offsets
-fn map_floors(instructions: &str) -> impl Iterator<Item = i32> + '_ {
-    instructions.trim().chars().map(|c| match c {
-        '(' => 1,
-        ')' => -1,
-        err => panic!("Don't know what to do with a {err}"),
+fn map_floors(instructions: &str) -> impl Iterator<Item = Result<i32, Error>> + '_ {
+    instructions.trim().chars().enumerate().map(|(index, c)| match c {
+        '(' => Ok(1),
+        ')' => Ok(-1),
+        found => Err(Error::Unknown { position: index + 1, found }),
     })
 }
 
@@ -15,14 +39,15 @@ fn map_floors(instructions: &str) -> impl Iterator<Item = i32> + '_ {
 /// Examples
 ///
 /// ```
-/// # use aoc2015::day1::find_floor;
-/// assert_eq!(find_floor("(())"), 0);
-/// assert_eq!(find_floor("()()"), 0);
-/// assert_eq!(find_floor("((("), 3);
-/// assert_eq!(find_floor(")))"), -3);
-/// assert_eq!(find_floor("))((((("), 3);
+/// # use aoc2015::day1::*;
+/// assert_eq!(find_floor("(())"), Ok(0));
+/// assert_eq!(find_floor("()()"), Ok(0));
+/// assert_eq!(find_floor("((("), Ok(3));
+/// assert_eq!(find_floor(")))"), Ok(-3));
+/// assert_eq!(find_floor("))((((("), Ok(3));
+/// assert_eq!(find_floor("(x"), Err(Error::Unknown { position: 2, found: 'x' }));
 /// ```
-pub fn find_floor(instructions: &str) -> i32 {
+pub fn find_floor(instructions: &str) -> Result<i32, Error> {
     map_floors(instructions).sum()
 }
 
@@ -32,35 +57,60 @@ pub fn find_floor(instructions: &str) -> i32 {
 /// Examples
 ///
 /// ```
-/// # use aoc2015::day1::find_basement;
-/// assert_eq!(find_basement(")"), 1);
+/// # use aoc2015::day1::*;
+/// assert_eq!(find_basement(")"), Ok(1));
+/// assert_eq!(find_basement("(()))x"), Ok(5));
+/// assert_eq!(find_basement("(x))"), Err(Error::Unknown { position: 2, found: 'x' }));
+/// assert_eq!(find_basement("(()"), Err(Error::NoBasement));
 /// ```
-pub fn find_basement(instructions: &str) -> usize {
-    map_floors(instructions)
-        .scan(0, |state, x| {
-            *state += x;
-            Some(*state)
-        })
-        .enumerate()
-        .find(|x| x.1 == -1)
-        .expect("We should have hit the basement once according to the problem")
-        .0
-        + 1
+pub fn find_basement(instructions: &str) -> Result<usize, Error> {
+    let mut floor = 0;
+    for (index, offset) in map_floors(instructions).enumerate() {
+        floor += offset?;
+        if floor == -1 {
+            return Ok(index + 1);
+        }
+    }
+
+    Err(Error::NoBasement)
 }
 
 #[cfg(test)]
 mod answers {
     use super::*;
-
-    static INPUT: &str = include_str!("./input/day1.txt");
+    use crate::input::bundled;
 
     #[test]
     pub fn problem1() {
-        assert_eq!(find_floor(INPUT), 74);
+        assert_eq!(find_floor(bundled(1).unwrap()), Ok(74));
     }
 
     #[test]
     pub fn problem2() {
-        assert_eq!(find_basement(INPUT), 1795);
+        assert_eq!(find_basement(bundled(1).unwrap()), Ok(1795));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn floor_is_ups_minus_downs(instructions in "[()]{0,200}") {
+            let ups = instructions.matches('(').count() as i32;
+            let downs = instructions.matches(')').count() as i32;
+            prop_assert_eq!(find_floor(&instructions), Ok(ups - downs));
+        }
+
+        #[test]
+        fn basement_is_first_visit_to_floor_minus_one(instructions in "[()]{0,200}\\)") {
+            let all_the_way_down = format!("{instructions}{}", ")".repeat(instructions.len()));
+            let position = find_basement(&all_the_way_down).unwrap();
+
+            prop_assert_eq!(find_floor(&all_the_way_down[..position]), Ok(-1));
+            prop_assert!((0..position).all(|end| find_floor(&all_the_way_down[..end]).unwrap() >= 0));
+        }
     }
 }
@@ -0,0 +1,301 @@
+//! Day 10 - Elves Look, Elves Say
+
+use std::fmt;
+
+mod elements;
+
+pub use elements::*;
+
+/// A seed that isn't a string of digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedError {
+    Empty,
+    NotADigit { position: usize, found: char },
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedError::Empty => write!(f, "There's no sequence to start from"),
+            SeedError::NotADigit { position, found } => write!(f, "Expected a digit at position {position}, found {found:?}"),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+/// The digits to start from, without any surrounding whitespace
+///
+/// ```
+/// # use aoc2015::day10::*;
+/// assert_eq!(parse_seed("1113222113\n"), Ok(&b"1113222113"[..]));
+/// assert_eq!(parse_seed("12a"), Err(SeedError::NotADigit { position: 2, found: 'a' }));
+/// assert_eq!(parse_seed(" "), Err(SeedError::Empty));
+/// ```
+pub fn parse_seed(input: &str) -> Result<&[u8], SeedError> {
+    let seed = input.trim();
+    if let Some((position, found)) = seed.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(SeedError::NotADigit { position, found });
+    }
+
+    match seed.is_empty() {
+        true => Err(SeedError::Empty),
+        false => Ok(seed.as_bytes()),
+    }
+}
+
+/// Read `digits` aloud, writing what was said over `out`
+///
+/// ```
+/// # use aoc2015::day10::*;
+/// let mut out = Vec::new();
+/// look_and_say(b"111221", &mut out);
+/// assert_eq!(out, b"312211");
+///
+/// look_and_say(b"777777777777", &mut out);
+/// assert_eq!(out, b"127");
+/// ```
+pub fn look_and_say(digits: &[u8], out: &mut Vec<u8>) {
+    out.clear();
+    let mut rest = digits;
+    while let Some(&digit) = rest.first() {
+        let run = rest.iter().take_while(|&&d| d == digit).count();
+        match u8::try_from(run) {
+            Ok(run @ 0..=9) => out.push(b'0' + run),
+            _ => out.extend_from_slice(run.to_string().as_bytes()),
+        }
+
+        out.push(digit);
+        rest = &rest[run..];
+    }
+}
+
+/// Look-and-say `rounds` times, keeping the whole string
+///
+/// ```
+/// # use aoc2015::day10::*;
+/// assert_eq!(iterate(b"1", 5), b"312211");
+/// assert_eq!(iterate(b"1", 0), b"1");
+/// ```
+pub fn iterate(seed: &[u8], rounds: usize) -> Vec<u8> {
+    let mut current = seed.to_vec();
+    let mut next = Vec::with_capacity(seed.len() * 2);
+    for _ in 0..rounds {
+        look_and_say(&current, &mut next);
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    current
+}
+
+/// Whether `left` followed by the element numbered `right` stay apart
+/// forever. The last digit of `left` never changes, so they do as long as
+/// it never matches the first digit of whatever `right` turns into
+fn splits(left: &Element, right: u8) -> bool {
+    let last = left.sequence.as_bytes().last();
+    let mut seen = 0u128;
+    let mut number = right;
+    loop {
+        let element = &ELEMENTS[usize::from(number) - 1];
+        if element.sequence.as_bytes().first() == last {
+            return false;
+        }
+        if seen & (1 << number) != 0 {
+            return true;
+        }
+
+        seen |= 1 << number;
+        number = element.decay[0];
+    }
+}
+
+/// How many of each element a string is made of. The elements never
+/// interact, so this is all it takes to work out how long the string gets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compound {
+    counts: [u64; 92],
+}
+
+impl Compound {
+    /// Split a string up into elements, if it's made of nothing else
+    ///
+    /// ```
+    /// # use aoc2015::day10::*;
+    /// let compound = Compound::decompose(b"3113322113").unwrap();
+    /// assert_eq!(compound.count(Element::named("Bi").unwrap()), 1);
+    ///
+    /// let compound = Compound::decompose(b"223113322113").unwrap();
+    /// assert_eq!(compound.count(Element::named("H").unwrap()), 1);
+    /// assert_eq!(compound.len(), Some(12));
+    ///
+    /// // Calcium is `12` and hydrogen is `22`, but together the 2s run
+    /// // into each other
+    /// assert_eq!(Compound::decompose(b"1222"), None);
+    /// assert_eq!(Compound::decompose(b"1"), None);
+    /// assert_eq!(Compound::decompose(b"31224"), None);
+    /// ```
+    pub fn decompose(digits: &[u8]) -> Option<Self> {
+        // The elements that everything from each position can start with
+        let mut starts = vec![Vec::new(); digits.len() + 1];
+        for position in (0..digits.len()).rev() {
+            for element in &ELEMENTS {
+                let end = position + element.sequence.len();
+                let fits = digits[position..].starts_with(element.sequence.as_bytes());
+                if fits && (end == digits.len() || starts[end].iter().any(|&next| splits(element, next))) {
+                    starts[position].push(element.number);
+                }
+            }
+        }
+
+        let mut counts = [0; 92];
+        let mut position = 0;
+        let mut previous: Option<&Element> = None;
+        while position < digits.len() {
+            let &number = starts[position]
+                .iter()
+                .find(|&&number| previous.map_or(true, |previous| splits(previous, number)))?;
+
+            let element = &ELEMENTS[usize::from(number) - 1];
+            counts[usize::from(number) - 1] += 1;
+            position += element.sequence.len();
+            previous = Some(element);
+        }
+
+        Some(Self { counts })
+    }
+
+    /// What the compound turns into after a round of look-and-say, if
+    /// there still aren't too many of any element to count in a `u64`.
+    /// The counts grow by about 30% a round, so that's over a hundred
+    /// rounds for most seeds
+    ///
+    /// ```
+    /// # use aoc2015::day10::*;
+    /// let compound = Compound::decompose(b"22").unwrap().step().unwrap();
+    /// assert_eq!(compound.len(), Some(2));
+    /// ```
+    pub fn step(&self) -> Option<Self> {
+        let mut counts = [0u64; 92];
+        for (element, &count) in ELEMENTS.iter().zip(&self.counts) {
+            for &product in element.decay {
+                let total = &mut counts[usize::from(product) - 1];
+                *total = total.checked_add(count)?;
+            }
+        }
+
+        Some(Self { counts })
+    }
+
+    pub fn count(&self, element: &Element) -> u64 {
+        self.counts[usize::from(element.number) - 1]
+    }
+
+    /// How long the string is, if that fits in a `u64`
+    pub fn len(&self) -> Option<u64> {
+        ELEMENTS
+            .iter()
+            .zip(&self.counts)
+            .try_fold(0u64, |total, (element, &count)| total.checked_add((element.sequence.len() as u64).checked_mul(count)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+}
+
+/// How long the string is after `rounds` rounds of look-and-say. This
+/// works on the string itself until it's nothing but elements, which
+/// only takes a few rounds, then just keeps count of the elements. A
+/// seed ending in a digit over 3 never gets there, since the last digit
+/// never changes, so has to be worked through the slow way. There's no
+/// length once it's too long to count in a `u64`
+///
+/// ```
+/// # use aoc2015::day10::*;
+/// assert_eq!(length_after(b"1", 5), Some(6));
+/// assert_eq!(length_after(b"1", 30), Some(iterate(b"1", 30).len() as u64));
+/// assert_eq!(length_after(b"4", 3), Some(4));
+/// assert_eq!(length_after(b"1", 200), None);
+/// ```
+pub fn length_after(seed: &[u8], rounds: usize) -> Option<u64> {
+    let mut digits = seed.to_vec();
+    let mut next = Vec::with_capacity(seed.len() * 2);
+    for round in 0..rounds {
+        // Elements only ever have 1s, 2s and 3s in them
+        if digits.iter().all(|digit| (b'1'..=b'3').contains(digit)) {
+            if let Some(compound) = Compound::decompose(&digits) {
+                return (round..rounds).try_fold(compound, |compound, _| compound.step())?.len();
+            }
+        }
+
+        look_and_say(&digits, &mut next);
+        std::mem::swap(&mut digits, &mut next);
+    }
+
+    Some(digits.len() as u64)
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+
+    fn seed() -> &'static [u8] {
+        parse_seed(bundled(10).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn problem1() {
+        assert_eq!(iterate(seed(), 40).len(), 329356);
+        assert_eq!(length_after(seed(), 40), Some(329356));
+    }
+
+    #[test]
+    pub fn problem2() {
+        assert_eq!(length_after(seed(), 50), Some(4666278));
+    }
+
+    #[test]
+    pub fn a_hundred_rounds() {
+        assert_eq!(length_after(seed(), 100), Some(2665316511734));
+    }
+
+    #[test]
+    pub fn lengths_stop_at_u64() {
+        assert_eq!(length_after(b"1", 164), Some(15578352330725973270));
+        assert_eq!(length_after(b"1", 165), None);
+    }
+
+    #[test]
+    pub fn elements_decay_as_they_say() {
+        let mut said = Vec::new();
+        for element in &ELEMENTS {
+            look_and_say(element.sequence.as_bytes(), &mut said);
+            let products = element.decay.iter().map(|&number| Element::get(number).unwrap().sequence).collect::<String>();
+            assert_eq!(String::from_utf8(said.clone()).unwrap(), products, "{}", element.name);
+            assert_eq!(Compound::decompose(element.sequence.as_bytes()).map(|c| c.count(element)), Some(1), "{}", element.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn elements_give_the_same_lengths(seed in "[1-9]{1,8}", rounds in 0..20usize) {
+            prop_assert_eq!(length_after(seed.as_bytes(), rounds), Some(iterate(seed.as_bytes(), rounds).len() as u64));
+        }
+
+        #[test]
+        fn compounds_keep_up_with_the_string(seed in "[1-3]{1,8}", skip in 0..10usize, rounds in 0..10usize) {
+            let digits = iterate(seed.as_bytes(), skip);
+            if let Some(compound) = Compound::decompose(&digits) {
+                let stepped = (0..rounds).try_fold(compound, |compound, _| compound.step()).unwrap();
+                prop_assert_eq!(stepped.len(), Some(iterate(&digits, rounds).len() as u64));
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Conway's 92 elements: the strings that look-and-say never splits up,
+//! which every string eventually turns into a mixture of
+
+/// One of the elements, and what it turns into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Element {
+    /// The atomic number, which is also where it is in [`ELEMENTS`],
+    /// counting from one
+    pub number: u8,
+    pub name: &'static str,
+    pub sequence: &'static str,
+
+    /// The atomic numbers of the elements it decays into after one round,
+    /// in order
+    pub decay: &'static [u8],
+}
+
+impl Element {
+    /// Look up an element by its atomic number
+    ///
+    /// ```
+    /// # use aoc2015::day10::*;
+    /// assert_eq!(Element::get(1).unwrap().sequence, "22");
+    /// assert_eq!(Element::get(92).unwrap().name, "U");
+    /// assert_eq!(Element::get(93), None);
+    /// ```
+    pub fn get(number: u8) -> Option<&'static Element> {
+        ELEMENTS.get(usize::from(number).checked_sub(1)?)
+    }
+
+    /// Look up an element by its symbol
+    ///
+    /// ```
+    /// # use aoc2015::day10::*;
+    /// assert_eq!(Element::named("Bi").unwrap().sequence, "3113322113");
+    /// ```
+    pub fn named(name: &str) -> Option<&'static Element> {
+        ELEMENTS.iter().find(|element| element.name == name)
+    }
+}
+
+/// Every element, in order of atomic number
+pub const ELEMENTS: [Element; 92] = [
+    Element { number: 1, name: "H", sequence: "22", decay: &[1] },
+    Element { number: 2, name: "He", sequence: "13112221133211322112211213322112", decay: &[72, 91, 1, 20, 3] },
+    Element { number: 3, name: "Li", sequence: "312211322212221121123222112", decay: &[2] },
+    Element { number: 4, name: "Be", sequence: "111312211312113221133211322112211213322112", decay: &[32, 20, 3] },
+    Element { number: 5, name: "B", sequence: "1321132122211322212221121123222112", decay: &[4] },
+    Element { number: 6, name: "C", sequence: "3113112211322112211213322112", decay: &[5] },
+    Element { number: 7, name: "N", sequence: "111312212221121123222112", decay: &[6] },
+    Element { number: 8, name: "O", sequence: "132112211213322112", decay: &[7] },
+    Element { number: 9, name: "F", sequence: "31121123222112", decay: &[8] },
+    Element { number: 10, name: "Ne", sequence: "111213322112", decay: &[9] },
+    Element { number: 11, name: "Na", sequence: "123222112", decay: &[10] },
+    Element { number: 12, name: "Mg", sequence: "3113322112", decay: &[61, 11] },
+    Element { number: 13, name: "Al", sequence: "1113222112", decay: &[12] },
+    Element { number: 14, name: "Si", sequence: "1322112", decay: &[13] },
+    Element { number: 15, name: "P", sequence: "311311222112", decay: &[67, 14] },
+    Element { number: 16, name: "S", sequence: "1113122112", decay: &[15] },
+    Element { number: 17, name: "Cl", sequence: "132112", decay: &[16] },
+    Element { number: 18, name: "Ar", sequence: "3112", decay: &[17] },
+    Element { number: 19, name: "K", sequence: "1112", decay: &[18] },
+    Element { number: 20, name: "Ca", sequence: "12", decay: &[19] },
+    Element { number: 21, name: "Sc", sequence: "3113112221133112", decay: &[67, 91, 1, 20, 27] },
+    Element { number: 22, name: "Ti", sequence: "11131221131112", decay: &[21] },
+    Element { number: 23, name: "V", sequence: "13211312", decay: &[22] },
+    Element { number: 24, name: "Cr", sequence: "31132", decay: &[23] },
+    Element { number: 25, name: "Mn", sequence: "111311222112", decay: &[24, 14] },
+    Element { number: 26, name: "Fe", sequence: "13122112", decay: &[25] },
+    Element { number: 27, name: "Co", sequence: "32112", decay: &[26] },
+    Element { number: 28, name: "Ni", sequence: "11133112", decay: &[30, 27] },
+    Element { number: 29, name: "Cu", sequence: "131112", decay: &[28] },
+    Element { number: 30, name: "Zn", sequence: "312", decay: &[29] },
+    Element { number: 31, name: "Ga", sequence: "13221133122211332", decay: &[63, 20, 89, 1, 20, 30] },
+    Element { number: 32, name: "Ge", sequence: "31131122211311122113222", decay: &[67, 31] },
+    Element { number: 33, name: "As", sequence: "11131221131211322113322112", decay: &[32, 11] },
+    Element { number: 34, name: "Se", sequence: "13211321222113222112", decay: &[33] },
+    Element { number: 35, name: "Br", sequence: "3113112211322112", decay: &[34] },
+    Element { number: 36, name: "Kr", sequence: "11131221222112", decay: &[35] },
+    Element { number: 37, name: "Rb", sequence: "1321122112", decay: &[36] },
+    Element { number: 38, name: "Sr", sequence: "3112112", decay: &[37] },
+    Element { number: 39, name: "Y", sequence: "1112133", decay: &[38, 92] },
+    Element { number: 40, name: "Zr", sequence: "12322211331222113112211", decay: &[39, 1, 20, 43] },
+    Element { number: 41, name: "Nb", sequence: "1113122113322113111221131221", decay: &[68, 40] },
+    Element { number: 42, name: "Mo", sequence: "13211322211312113211", decay: &[41] },
+    Element { number: 43, name: "Tc", sequence: "311322113212221", decay: &[42] },
+    Element { number: 44, name: "Ru", sequence: "132211331222113112211", decay: &[63, 20, 43] },
+    Element { number: 45, name: "Rh", sequence: "311311222113111221131221", decay: &[67, 44] },
+    Element { number: 46, name: "Pd", sequence: "111312211312113211", decay: &[45] },
+    Element { number: 47, name: "Ag", sequence: "132113212221", decay: &[46] },
+    Element { number: 48, name: "Cd", sequence: "3113112211", decay: &[47] },
+    Element { number: 49, name: "In", sequence: "11131221", decay: &[48] },
+    Element { number: 50, name: "Sn", sequence: "13211", decay: &[49] },
+    Element { number: 51, name: "Sb", sequence: "3112221", decay: &[61, 50] },
+    Element { number: 52, name: "Te", sequence: "1322113312211", decay: &[63, 20, 51] },
+    Element { number: 53, name: "I", sequence: "311311222113111221", decay: &[67, 52] },
+    Element { number: 54, name: "Xe", sequence: "11131221131211", decay: &[53] },
+    Element { number: 55, name: "Cs", sequence: "13211321", decay: &[54] },
+    Element { number: 56, name: "Ba", sequence: "311311", decay: &[55] },
+    Element { number: 57, name: "La", sequence: "11131", decay: &[56] },
+    Element { number: 58, name: "Ce", sequence: "1321133112", decay: &[57, 1, 20, 27] },
+    Element { number: 59, name: "Pr", sequence: "31131112", decay: &[58] },
+    Element { number: 60, name: "Nd", sequence: "111312", decay: &[59] },
+    Element { number: 61, name: "Pm", sequence: "132", decay: &[60] },
+    Element { number: 62, name: "Sm", sequence: "311332", decay: &[61, 20, 30] },
+    Element { number: 63, name: "Eu", sequence: "1113222", decay: &[62] },
+    Element { number: 64, name: "Gd", sequence: "13221133112", decay: &[63, 20, 27] },
+    Element { number: 65, name: "Tb", sequence: "3113112221131112", decay: &[67, 64] },
+    Element { number: 66, name: "Dy", sequence: "111312211312", decay: &[65] },
+    Element { number: 67, name: "Ho", sequence: "1321132", decay: &[66] },
+    Element { number: 68, name: "Er", sequence: "311311222", decay: &[67, 61] },
+    Element { number: 69, name: "Tm", sequence: "11131221133112", decay: &[68, 20, 27] },
+    Element { number: 70, name: "Yb", sequence: "1321131112", decay: &[69] },
+    Element { number: 71, name: "Lu", sequence: "311312", decay: &[70] },
+    Element { number: 72, name: "Hf", sequence: "11132", decay: &[71] },
+    Element { number: 73, name: "Ta", sequence: "13112221133211322112211213322113", decay: &[72, 91, 1, 20, 74] },
+    Element { number: 74, name: "W", sequence: "312211322212221121123222113", decay: &[73] },
+    Element { number: 75, name: "Re", sequence: "111312211312113221133211322112211213322113", decay: &[32, 20, 74] },
+    Element { number: 76, name: "Os", sequence: "1321132122211322212221121123222113", decay: &[75] },
+    Element { number: 77, name: "Ir", sequence: "3113112211322112211213322113", decay: &[76] },
+    Element { number: 78, name: "Pt", sequence: "111312212221121123222113", decay: &[77] },
+    Element { number: 79, name: "Au", sequence: "132112211213322113", decay: &[78] },
+    Element { number: 80, name: "Hg", sequence: "31121123222113", decay: &[79] },
+    Element { number: 81, name: "Tl", sequence: "111213322113", decay: &[80] },
+    Element { number: 82, name: "Pb", sequence: "123222113", decay: &[81] },
+    Element { number: 83, name: "Bi", sequence: "3113322113", decay: &[61, 82] },
+    Element { number: 84, name: "Po", sequence: "1113222113", decay: &[83] },
+    Element { number: 85, name: "At", sequence: "1322113", decay: &[84] },
+    Element { number: 86, name: "Rn", sequence: "311311222113", decay: &[67, 85] },
+    Element { number: 87, name: "Fr", sequence: "1113122113", decay: &[86] },
+    Element { number: 88, name: "Ra", sequence: "132113", decay: &[87] },
+    Element { number: 89, name: "Ac", sequence: "3113", decay: &[88] },
+    Element { number: 90, name: "Th", sequence: "1113", decay: &[89] },
+    Element { number: 91, name: "Pa", sequence: "13", decay: &[90] },
+    Element { number: 92, name: "U", sequence: "3", decay: &[91] },
+];
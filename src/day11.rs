@@ -0,0 +1,296 @@
+//! Day 11 - Corporate Policy
+
+use std::fmt;
+
+use crate::predicates::{distinct_pairs, first_of, has_straight};
+
+/// A rule that a password has to follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// A run of at least this many letters going up one at a time
+    Straight(usize),
+
+    /// None of these letters, anywhere
+    Forbidden(&'static [u8]),
+
+    /// At least this many different letters doubled up
+    Pairs(usize),
+}
+
+impl Rule {
+    /// Whether a password follows the rule
+    ///
+    /// ```
+    /// # use aoc2015::day11::*;
+    /// assert!(Rule::Straight(3).check(b"hijklmmn"));
+    /// assert!(!Rule::Forbidden(b"iol").check(b"hijklmmn"));
+    /// assert!(Rule::Pairs(2).check(b"abbceffg"));
+    /// assert!(!Rule::Pairs(2).check(b"abbcegjk"));
+    /// ```
+    pub fn check(&self, password: &[u8]) -> bool {
+        match *self {
+            Rule::Straight(length) => has_straight(password, length),
+            Rule::Forbidden(letters) => first_of(password, letters).is_none(),
+            Rule::Pairs(count) => distinct_pairs(password) >= count,
+        }
+    }
+
+    /// Whether any password of this length could follow the rule
+    fn fits(&self, length: usize) -> bool {
+        match *self {
+            Rule::Straight(straight) => straight <= length && straight <= 26,
+            Rule::Forbidden(_) => true,
+            Rule::Pairs(count) => count.checked_mul(2).is_some_and(|letters| letters <= length) && count <= 26,
+        }
+    }
+
+    /// Whether no password starting with `prefix`, with `remaining` more
+    /// letters after it, could follow the rule. This only has to be sure
+    /// when it says yes, so it's generous about what the rest could be
+    ///
+    /// ```
+    /// # use aoc2015::day11::*;
+    /// assert!(Rule::Pairs(2).hopeless(b"abcdef", 2));
+    /// assert!(!Rule::Pairs(2).hopeless(b"abcdee", 2));
+    /// assert!(Rule::Straight(3).hopeless(b"acegi", 1));
+    /// assert!(!Rule::Straight(3).hopeless(b"acegh", 1));
+    /// assert!(Rule::Forbidden(b"iol").hopeless(b"hi", 6));
+    /// ```
+    pub fn hopeless(&self, prefix: &[u8], remaining: usize) -> bool {
+        match *self {
+            Rule::Straight(length) => {
+                // The best the rest can do is carry on the run at the end
+                let run = 1 + prefix.windows(2).rev().take_while(|w| w[0].checked_add(1) == Some(w[1])).count();
+                let run = if prefix.is_empty() { 0 } else { run };
+                !has_straight(prefix, length) && run + remaining < length
+            }
+            Rule::Forbidden(letters) => first_of(prefix, letters).is_some(),
+            Rule::Pairs(count) => {
+                // Pairs of different letters can't overlap, so the rest
+                // (and the letter before it) can't fit in more than this
+                let more = (remaining + usize::from(!prefix.is_empty())) / 2;
+                distinct_pairs(prefix) + more < count
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Straight(length) => write!(f, "a straight of {length} letters"),
+            Rule::Forbidden(letters) => {
+                let letters = letters.iter().map(|&letter| char::from(letter).to_string()).collect::<Vec<_>>();
+                write!(f, "none of {}", letters.join(", "))
+            }
+            Rule::Pairs(count) => write!(f, "{count} different pairs"),
+        }
+    }
+}
+
+/// The rules from the new Security-Elf
+pub const SANTA: [Rule; 3] = [Rule::Straight(3), Rule::Forbidden(b"iol"), Rule::Pairs(2)];
+
+/// The first rule a password breaks, if any
+///
+/// ```
+/// # use aoc2015::day11::*;
+/// assert_eq!(broken_rule(b"hijklmmn", &SANTA), Some(Rule::Forbidden(b"iol")));
+/// assert_eq!(broken_rule(b"abbceffg", &SANTA), Some(Rule::Straight(3)));
+/// assert_eq!(broken_rule(b"abcdffaa", &SANTA), None);
+/// ```
+pub fn broken_rule(password: &[u8], rules: &[Rule]) -> Option<Rule> {
+    rules.iter().find(|rule| !rule.check(password)).copied()
+}
+
+/// A password that can't be used, or can't be followed by another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordError {
+    Empty,
+    NotLowercase { position: usize, found: char },
+
+    /// Every password after this one, up to `zzz...`, breaks a rule
+    Exhausted,
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::Empty => write!(f, "There's no password"),
+            PasswordError::NotLowercase { position, found } => {
+                write!(f, "Expected a lowercase letter at position {position}, found {found:?}")
+            }
+            PasswordError::Exhausted => write!(f, "There are no more passwords that follow the rules"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+/// The password, without any surrounding whitespace
+///
+/// ```
+/// # use aoc2015::day11::*;
+/// assert_eq!(parse_password("abcdefgh\n"), Ok(&b"abcdefgh"[..]));
+/// assert_eq!(parse_password("abcDefgh"), Err(PasswordError::NotLowercase { position: 3, found: 'D' }));
+/// ```
+pub fn parse_password(input: &str) -> Result<&[u8], PasswordError> {
+    let password = input.trim();
+    if let Some((position, found)) = password.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(PasswordError::NotLowercase { position, found });
+    }
+
+    match password.is_empty() {
+        true => Err(PasswordError::Empty),
+        false => Ok(password.as_bytes()),
+    }
+}
+
+/// Count up by one, like an odometer with `a` to `z` on each wheel. Says
+/// whether it managed to without going all the way round to `aaa...`
+///
+/// ```
+/// # use aoc2015::day11::*;
+/// let mut password = *b"xz";
+/// assert!(increment(&mut password));
+/// assert_eq!(&password, b"ya");
+///
+/// let mut password = *b"zz";
+/// assert!(!increment(&mut password));
+/// assert_eq!(&password, b"aa");
+/// ```
+pub fn increment(password: &mut [u8]) -> bool {
+    for letter in password.iter_mut().rev() {
+        if *letter < b'z' {
+            *letter += 1;
+            return true;
+        }
+
+        *letter = b'a';
+    }
+
+    false
+}
+
+/// Set everything after the shortest prefix that can't lead anywhere to
+/// `z`, so the next increment jumps past every password starting with it
+fn skip_hopeless(password: &mut [u8], rules: &[Rule]) {
+    let length = password.len();
+    let hopeless = (1..length).find(|&end| rules.iter().any(|rule| rule.hopeless(&password[..end], length - end)));
+    if let Some(end) = hopeless {
+        password[end..].fill(b'z');
+    }
+}
+
+/// Jump past every password with a forbidden letter in it, all at once,
+/// by counting up the first forbidden letter and starting everything
+/// after it again from `a`. Says whether it managed to without going all
+/// the way round
+fn skip_forbidden(password: &mut [u8], forbidden: &[u8]) -> bool {
+    while let Some(position) = first_of(password, forbidden) {
+        password[position + 1..].fill(b'a');
+        if !increment(&mut password[..=position]) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// The next password after `current` that follows all the rules.
+/// Passwords are skipped in bulk wherever a rule rules out everything
+/// that starts a certain way, so it only steps through them one at a time
+/// over the last few letters
+///
+/// ```
+/// # use aoc2015::day11::*;
+/// assert_eq!(next_password("abcdefgh", &SANTA), Ok("abcdffaa".to_string()));
+/// assert_eq!(next_password("ghijklmn", &SANTA), Ok("ghjaabcc".to_string()));
+/// assert_eq!(next_password("zzzzzzzz", &SANTA), Err(PasswordError::Exhausted));
+/// assert_eq!(next_password("abcd", &SANTA), Err(PasswordError::Exhausted));
+/// ```
+pub fn next_password(current: &str, rules: &[Rule]) -> Result<String, PasswordError> {
+    let mut password = parse_password(current)?.to_vec();
+    if !rules.iter().all(|rule| rule.fits(password.len())) {
+        return Err(PasswordError::Exhausted);
+    }
+
+    let forbidden = rules
+        .iter()
+        .flat_map(|rule| match rule {
+            Rule::Forbidden(letters) => *letters,
+            _ => &[],
+        })
+        .copied()
+        .collect::<Vec<_>>();
+
+    loop {
+        if !increment(&mut password) || !skip_forbidden(&mut password, &forbidden) {
+            return Err(PasswordError::Exhausted);
+        }
+
+        if broken_rule(&password, rules).is_none() {
+            return Ok(String::from_utf8(password).expect("Passwords are only lowercase letters"));
+        }
+
+        skip_hopeless(&mut password, rules);
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+    use test_case::test_case;
+
+    #[test_case("abcdefgh" => "abcdffaa"; "sample data 1")]
+    #[test_case("ghijklmn" => "ghjaabcc"; "sample data 2")]
+    #[test_case(bundled(11).unwrap() => "xtmwwxyy"; "problem 1 data")]
+    pub fn problem1(input: &str) -> String {
+        next_password(input, &SANTA).unwrap()
+    }
+
+    #[test_case(bundled(11).unwrap() => "xtmxxyzz"; "problem 2 data")]
+    pub fn problem2(input: &str) -> String {
+        next_password(&next_password(input, &SANTA).unwrap(), &SANTA).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Try every password in turn, without skipping anything
+    fn one_at_a_time(current: &str, rules: &[Rule]) -> Option<String> {
+        let mut password = current.as_bytes().to_vec();
+        while increment(&mut password) {
+            if broken_rule(&password, rules).is_none() {
+                return String::from_utf8(password).ok();
+            }
+        }
+
+        None
+    }
+
+    proptest! {
+        #[test]
+        fn skipping_misses_nothing(current in "[a-z]{3,5}", pairs in 0..2usize) {
+            let rules = [Rule::Forbidden(b"iol"), Rule::Pairs(pairs), Rule::Straight(2)];
+            prop_assert_eq!(next_password(&current, &rules).ok(), one_at_a_time(&current, &rules));
+        }
+    }
+
+    proptest! {
+        // Valid passwords can be a long way apart, so don't look for too many
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn next_passwords_follow_the_rules(current in "[a-z]{8}") {
+            if let Ok(next) = next_password(&current, &SANTA) {
+                prop_assert!(next > current);
+                prop_assert_eq!(broken_rule(next.as_bytes(), &SANTA), None);
+            }
+        }
+    }
+}
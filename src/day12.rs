@@ -0,0 +1,372 @@
+//! Day 12 - JSAbacusFramework.io
+
+use std::fmt;
+
+use crate::prelude::*;
+use nom::{IResult, branch::alt, bytes::complete::{is_not, tag, take_while_m_n}};
+use nom::character::complete::{char, digit0, digit1, one_of};
+use nom::combinator::{all_consuming, cut, map, map_opt, map_res, opt, recognize, value, verify};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{fold_many0, separated_list0};
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
+
+mod path;
+
+pub use path::*;
+
+/// How many arrays and objects can be inside each other. Anything
+/// deeper is an error, rather than a stack overflow
+pub const MAX_DEPTH: usize = 512;
+
+/// A JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+
+    /// The members of an object, in the order they were written
+    Object(Vec<(String, Value)>),
+}
+
+/// Where a document stopped making sense
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonError {
+    /// The byte offset into the document
+    pub position: usize,
+
+    /// What was found there, if the document hadn't already ended
+    pub found: Option<char>,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.found {
+            Some(found) => write!(f, "Unexpected {found:?} at position {}", self.position),
+            None => write!(f, "Unexpected end of the document at position {}", self.position),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// A bit of a string between quotes
+enum Fragment<'a> {
+    Literal(&'a str),
+    Escaped(char),
+}
+
+/// `\uXXXX`, or two of them for a character outside the basic plane
+fn unicode_escape(input: &str) -> IResult<&str, char> {
+    let unit = || preceded(tag("\\u"), map_res(take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()), |hex| u16::from_str_radix(hex, 16)));
+    let surrogates = verify(pair(unit(), unit()), |&(high, low)| (0xd800..0xdc00).contains(&high) && (0xdc00..0xe000).contains(&low));
+
+    alt((
+        map_opt(surrogates, |(high, low)| char::decode_utf16([high, low]).next()?.ok()),
+        map_opt(unit(), |unit| char::from_u32(u32::from(unit))),
+    ))(input)
+}
+
+fn escape(input: &str) -> IResult<&str, char> {
+    preceded(
+        char('\\'),
+        alt((
+            value('"', char('"')),
+            value('\\', char('\\')),
+            value('/', char('/')),
+            value('\u{8}', char('b')),
+            value('\u{c}', char('f')),
+            value('\n', char('n')),
+            value('\r', char('r')),
+            value('\t', char('t')),
+        )),
+    )(input)
+}
+
+fn string(input: &str) -> IResult<&str, String> {
+    let literal = verify(is_not("\"\\"), |s: &str| !s.chars().any(char::is_control));
+    let fragment = alt((map(literal, Fragment::Literal), map(unicode_escape, Fragment::Escaped), map(escape, Fragment::Escaped)));
+
+    delimited(
+        char('"'),
+        fold_many0(fragment, String::new, |mut string, fragment| {
+            match fragment {
+                Fragment::Literal(s) => string.push_str(s),
+                Fragment::Escaped(c) => string.push(c),
+            }
+            string
+        }),
+        cut(char('"')),
+    )(input)
+}
+
+fn number(input: &str) -> IResult<&str, f64> {
+    let integer = alt((tag("0"), recognize(pair(one_of("123456789"), digit0))));
+    let fraction = pair(char('.'), digit1);
+    let exponent = tuple((one_of("eE"), opt(one_of("+-")), digit1));
+    map_res(recognize(tuple((opt(char('-')), integer, opt(fraction), opt(exponent)))), str::parse)(input)
+}
+
+/// An array or object opening `depth` levels down, which is a failure
+/// if that's too deep
+fn open(bracket: char, depth: usize) -> impl Fn(&str) -> IResult<&str, char> {
+    move |input| match char(bracket)(input)? {
+        _ if depth >= MAX_DEPTH => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+        opened => Ok(opened),
+    }
+}
+
+fn array(input: &str, depth: usize) -> IResult<&str, Vec<Value>> {
+    let item = |input| json(input, depth + 1);
+    delimited(open('[', depth), separated_list0(char(','), item), cut(ws(char(']'))))(input)
+}
+
+fn object(input: &str, depth: usize) -> IResult<&str, Vec<(String, Value)>> {
+    let member = separated_pair(ws(string), cut(char(':')), cut(|input| json(input, depth + 1)));
+    delimited(open('{', depth), separated_list0(char(','), member), cut(ws(char('}'))))(input)
+}
+
+/// A value `depth` arrays and objects down
+fn bare(input: &str, depth: usize) -> IResult<&str, Value> {
+    alt((
+        value(Value::Null, tag("null")),
+        value(Value::Bool(true), tag("true")),
+        value(Value::Bool(false), tag("false")),
+        map(number, Value::Number),
+        map(string, Value::String),
+        map(|input| array(input, depth), Value::Array),
+        map(|input| object(input, depth), Value::Object),
+    ))(input)
+}
+
+/// A value with any whitespace around it. Once an array, object or string
+/// has started, anything wrong inside it is a failure rather than a
+/// reason to try something else, so errors point at the problem rather
+/// than the start of the document
+fn json(input: &str, depth: usize) -> IResult<&str, Value> {
+    ws(move |input| bare(input, depth))(input)
+}
+
+impl Value {
+    /// Parse a whole document. An array or object nested more than
+    /// [`MAX_DEPTH`] deep is an error at its opening bracket
+    ///
+    /// ```
+    /// # use aoc2015::day12::*;
+    /// let value = Value::parse(r#" {"a": [1, -2.5e1, true, null], "bé🎄": "x\ty"} "#).unwrap();
+    /// assert_eq!(value, Value::Object(vec![
+    ///     ("a".to_string(), Value::Array(vec![Value::Number(1.0), Value::Number(-25.0), Value::Bool(true), Value::Null])),
+    ///     ("bé🎄".to_string(), Value::String("x\ty".to_string())),
+    /// ]));
+    ///
+    /// assert_eq!(Value::parse("[1, 2,]"), Err(JsonError { position: 5, found: Some(',') }));
+    /// assert_eq!(Value::parse(r#"{"a": 01}"#), Err(JsonError { position: 7, found: Some('1') }));
+    /// assert_eq!(Value::parse("[1, 2"), Err(JsonError { position: 5, found: None }));
+    /// assert!(Value::parse(r#""\ud800""#).is_err());
+    ///
+    /// let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+    /// assert!(Value::parse(&nested(MAX_DEPTH)).is_ok());
+    /// assert_eq!(Value::parse(&nested(MAX_DEPTH + 1)), Err(JsonError { position: MAX_DEPTH, found: Some('[') }));
+    /// assert!(Value::parse(&nested(2000)).is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        match all_consuming(|input| json(input, 0))(text) {
+            Ok((_, value)) => Ok(value),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                Err(JsonError { position: text.len() - err.input.len(), found: err.input.chars().next() })
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("Only complete parsers are used"),
+        }
+    }
+
+    /// The values directly inside an array or object
+    pub fn children(&self) -> Box<dyn Iterator<Item = &Value> + '_> {
+        match self {
+            Value::Array(values) => Box::new(values.iter()),
+            Value::Object(members) => Box::new(members.iter().map(|(_, value)| value)),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// The value of a member of an object. If the key turns up more than
+    /// once, it's the last one that counts, like most parsers
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().rev().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+/// Decides which parts of a document to leave out
+pub trait Filter {
+    /// Whether to leave out a value, and everything inside it
+    fn prune(&self, value: &Value) -> bool;
+}
+
+impl<F: Fn(&Value) -> bool> Filter for F {
+    fn prune(&self, value: &Value) -> bool {
+        self(value)
+    }
+}
+
+/// Leaves nothing out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Everything;
+
+impl Filter for Everything {
+    fn prune(&self, _: &Value) -> bool {
+        false
+    }
+}
+
+/// Leaves out any object with a member that has the given value
+///
+/// ```
+/// # use aoc2015::day12::*;
+/// let red = ObjectsContaining(Value::String("red".to_string()));
+/// assert!(red.prune(&Value::parse(r#"{"c":"red","b":2}"#).unwrap()));
+/// assert!(!red.prune(&Value::parse(r#"[1,"red",5]"#).unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectsContaining(pub Value);
+
+impl Filter for ObjectsContaining {
+    fn prune(&self, value: &Value) -> bool {
+        matches!(value, Value::Object(members) if members.iter().any(|(_, member)| *member == self.0))
+    }
+}
+
+/// Add up every number in a document, apart from anything the filter
+/// leaves out
+///
+/// ```
+/// # use aoc2015::day12::*;
+/// let value = Value::parse(r#"[1,{"c":"red","b":2},3]"#).unwrap();
+/// assert_eq!(sum_numbers(&value, &Everything), 6.0);
+/// assert_eq!(sum_numbers(&value, &ObjectsContaining(Value::String("red".into()))), 4.0);
+/// assert_eq!(sum_numbers(&value, &|value: &Value| matches!(value, Value::Number(n) if *n > 2.0)), 3.0);
+/// ```
+pub fn sum_numbers(value: &Value, filter: &impl Filter) -> f64 {
+    let mut total = 0.0;
+    let mut unvisited = vec![value];
+    while let Some(value) = unvisited.pop() {
+        if filter.prune(value) {
+            continue;
+        }
+
+        match value {
+            Value::Number(n) => total += n,
+            value => unvisited.extend(value.children()),
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+    use test_case::test_case;
+
+    fn red() -> ObjectsContaining {
+        ObjectsContaining(Value::String("red".to_string()))
+    }
+
+    #[test_case("[1,2,3]" => 6.0; "sample data 1")]
+    #[test_case(r#"{"a":2,"b":4}"# => 6.0; "sample data 2")]
+    #[test_case("[[[3]]]" => 3.0; "sample data 3")]
+    #[test_case(r#"{"a":{"b":4},"c":-1}"# => 3.0; "sample data 4")]
+    #[test_case(r#"{"a":[-1,1]}"# => 0.0; "sample data 5")]
+    #[test_case(r#"[-1,{"a":1}]"# => 0.0; "sample data 6")]
+    #[test_case("[]" => 0.0; "sample data 7")]
+    #[test_case("{}" => 0.0; "sample data 8")]
+    #[test_case(bundled(12).unwrap() => 94471.0; "problem 1 data")]
+    pub fn problem1(input: &str) -> f64 {
+        sum_numbers(&Value::parse(input).unwrap(), &Everything)
+    }
+
+    #[test_case("[1,2,3]" => 6.0; "sample data 1")]
+    #[test_case(r#"[1,{"c":"red","b":2},3]"# => 4.0; "sample data 2")]
+    #[test_case(r#"{"d":"red","e":[1,2,3,4],"f":5}"# => 0.0; "sample data 3")]
+    #[test_case(r#"[1,"red",5]"# => 6.0; "sample data 4")]
+    #[test_case(bundled(12).unwrap() => 86155.0; "problem 2 data")]
+    pub fn problem2(input: &str) -> f64 {
+        sum_numbers(&Value::parse(input).unwrap(), &red())
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn document() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            (-1000..1000i32).prop_map(|n| Value::Number(f64::from(n))),
+            "[a-z\"\\\\\n é]{0,6}".prop_map(Value::String),
+        ];
+
+        leaf.prop_recursive(4, 32, 5, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..5).prop_map(Value::Array),
+                prop::collection::vec(("[a-z]{1,3}", inner), 0..5).prop_map(Value::Object),
+            ]
+        })
+    }
+
+    /// Write a value out as JSON, with some extra whitespace
+    fn write(value: &Value) -> String {
+        let string = |s: &str| {
+            let escaped = s.chars().map(|c| match c {
+                '"' => "\\\"".to_string(),
+                '\\' => "\\\\".to_string(),
+                '\n' => "\\n".to_string(),
+                c if !c.is_ascii() => format!("\\u{:04x}", u32::from(c)),
+                c => c.to_string(),
+            });
+            format!("\"{}\"", escaped.collect::<String>())
+        };
+
+        match value {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => string(s),
+            Value::Array(values) => format!("[ {} ]", values.iter().map(write).collect::<Vec<_>>().join(" , ")),
+            Value::Object(members) => {
+                let members = members.iter().map(|(key, value)| format!("{}: {}", string(key), write(value)));
+                format!("{{\n{}\n}}", members.collect::<Vec<_>>().join(",\n"))
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parses_what_it_writes(value in document()) {
+            prop_assert_eq!(Value::parse(&write(&value)), Ok(value));
+        }
+
+        #[test]
+        fn leaving_out_negatives_never_lowers_the_sum(value in document()) {
+            let negative = |value: &Value| matches!(value, Value::Number(n) if *n < 0.0);
+            prop_assert!(sum_numbers(&value, &negative) >= sum_numbers(&value, &Everything));
+        }
+
+        #[test]
+        fn paths_find_every_number(value in document()) {
+            // `$..*` finds everything but the document itself
+            let found = Path::parse("$..*").unwrap().query(&value);
+            let numbers = std::iter::once(&value).chain(found).filter_map(|value| match value {
+                Value::Number(n) => Some(n),
+                _ => None,
+            });
+            prop_assert_eq!(numbers.sum::<f64>(), sum_numbers(&value, &Everything));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Picking values out of a document with a subset of JSONPath
+//!
+//! A path starts at the root, `$`, and each segment after it picks out
+//! values from whatever the segment before it found:
+//!
+//! - `.key` or `["key"]` or `['key']`, a member of an object
+//! - `[2]`, an item in an array, or `[-1]` counting back from the end
+//! - `.*` or `[*]`, everything inside an array or object
+//! - `..` before any of those, looking at every level below rather than
+//!   just the next one down
+
+use std::fmt;
+
+use nom::IResult;
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, tag, take_while1};
+use nom::character::complete::{char, i64};
+use nom::combinator::{all_consuming, map, value};
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded};
+
+use super::{Value, string};
+
+/// Which values a segment picks out of each value it's given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    Key(String),
+
+    /// An item in an array, counting back from the end if it's negative
+    Index(i64),
+    Wildcard,
+}
+
+impl Selector {
+    /// The values inside `value` that the selector picks out
+    fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        match (self, value) {
+            (Selector::Key(key), value) => value.get(key).into_iter().collect(),
+            (&Selector::Index(index), Value::Array(values)) => {
+                let index = match index < 0 {
+                    true => usize::try_from(index.unsigned_abs()).ok().and_then(|back| values.len().checked_sub(back)),
+                    false => usize::try_from(index).ok(),
+                };
+                index.and_then(|index| values.get(index)).into_iter().collect()
+            }
+            (Selector::Index(_), _) => Vec::new(),
+            (Selector::Wildcard, value) => value.children().collect(),
+        }
+    }
+}
+
+/// One step along a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Pick from the values one level down
+    Child(Selector),
+
+    /// Pick from the values at every level down
+    Descendant(Selector),
+}
+
+/// A path that wasn't understood
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathError {
+    /// The byte offset into the path
+    pub position: usize,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Couldn't understand the path at position {}", self.position)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+fn name(input: &str) -> IResult<&str, Selector> {
+    map(take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-'), |name: &str| Selector::Key(name.to_string()))(input)
+}
+
+fn wildcard(input: &str) -> IResult<&str, Selector> {
+    value(Selector::Wildcard, char('*'))(input)
+}
+
+/// `[...]`, with a quoted key, an index or a wildcard
+fn bracketed(input: &str) -> IResult<&str, Selector> {
+    let quoted = alt((
+        map(string, Selector::Key),
+        map(delimited(char('\''), is_not("'"), char('\'')), |key: &str| Selector::Key(key.to_string())),
+    ));
+
+    delimited(char('['), alt((quoted, map(i64, Selector::Index), wildcard)), char(']'))(input)
+}
+
+fn segment(input: &str) -> IResult<&str, Segment> {
+    alt((
+        map(preceded(tag(".."), alt((name, wildcard, bracketed))), Segment::Descendant),
+        map(preceded(char('.'), alt((name, wildcard))), Segment::Child),
+        map(bracketed, Segment::Child),
+    ))(input)
+}
+
+/// Where to find values in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub segments: Vec<Segment>,
+}
+
+impl Path {
+    /// ```
+    /// # use aoc2015::day12::*;
+    /// let path = Path::parse("$.a..b[0]['c d'][*]").unwrap();
+    /// assert_eq!(path.segments, vec![
+    ///     Segment::Child(Selector::Key("a".to_string())),
+    ///     Segment::Descendant(Selector::Key("b".to_string())),
+    ///     Segment::Child(Selector::Index(0)),
+    ///     Segment::Child(Selector::Key("c d".to_string())),
+    ///     Segment::Child(Selector::Wildcard),
+    /// ]);
+    ///
+    /// assert_eq!(Path::parse("$.a.[0]"), Err(PathError { position: 3 }));
+    /// assert_eq!(Path::parse("a"), Err(PathError { position: 0 }));
+    /// ```
+    pub fn parse(path: &str) -> Result<Self, PathError> {
+        match all_consuming(preceded(char('$'), many0(segment)))(path) {
+            Ok((_, segments)) => Ok(Self { segments }),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(PathError { position: path.len() - err.input.len() }),
+            Err(nom::Err::Incomplete(_)) => unreachable!("Only complete parsers are used"),
+        }
+    }
+
+    /// Every value in `document` the path leads to. A `..` segment looks at
+    /// parents before their children
+    ///
+    /// ```
+    /// # use aoc2015::day12::*;
+    /// let document = Value::parse(r#"{"a": [{"b": 1}, {"b": [2, {"b": 3}]}], "b": 4}"#).unwrap();
+    /// let found = |path| Path::parse(path).unwrap().query(&document);
+    ///
+    /// assert_eq!(found("$.a[0].b"), vec![&Value::Number(1.0)]);
+    /// assert_eq!(found("$.a[-1].b[1]['b']"), vec![&Value::Number(3.0)]);
+    /// assert_eq!(found("$.a[*].b").len(), 2);
+    /// assert_eq!(found("$..b").len(), 4);
+    /// assert_eq!(found("$.a[2]"), Vec::<&Value>::new());
+    /// assert_eq!(found("$"), vec![&document]);
+    /// ```
+    pub fn query<'a>(&self, document: &'a Value) -> Vec<&'a Value> {
+        self.segments.iter().fold(vec![document], |found, segment| match segment {
+            Segment::Child(selector) => found.into_iter().flat_map(|value| selector.select(value)).collect(),
+            Segment::Descendant(selector) => {
+                let mut everything = Vec::new();
+                for value in found {
+                    descendants(value, &mut everything);
+                }
+                everything.into_iter().flat_map(|value| selector.select(value)).collect()
+            }
+        })
+    }
+}
+
+/// `value` and everything inside it, parents before their children
+fn descendants<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    let mut unvisited = vec![value];
+    while let Some(value) = unvisited.pop() {
+        out.push(value);
+
+        // Backwards, so the first child comes off the stack first
+        let start = unvisited.len();
+        unvisited.extend(value.children());
+        unvisited[start..].reverse();
+    }
+}
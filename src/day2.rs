@@ -1,6 +1,9 @@
 //! Day 2 - I Was Told There Would Be No Math
 
-use std::num::ParseIntError;
+use core::fmt;
+use core::num::ParseIntError;
+
+use alloc::vec::Vec;
 
 pub struct Present {
     pub l: u32,
@@ -8,20 +11,97 @@ pub struct Present {
     pub h: u32,
 }
 
-impl std::str::FromStr for Present {
-    type Err = ParseIntError;
+/// A present whose dimensions couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresentError {
+    /// There weren't exactly three dimensions
+    Dimensions(usize),
+
+    /// One of the dimensions wasn't a number
+    Side(ParseIntError),
+
+    /// The paper or ribbon needed wouldn't fit in a `u32`
+    TooBig,
+}
+
+impl fmt::Display for PresentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresentError::Dimensions(count) => write!(f, "Presents have 3 dimensions, not {count}"),
+            PresentError::Side(err) => write!(f, "Couldn't read the size of a side: {err}"),
+            PresentError::TooBig => write!(f, "The present is too big to measure"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PresentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PresentError::Dimensions(_) | PresentError::TooBig => None,
+            PresentError::Side(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParseIntError> for PresentError {
+    fn from(err: ParseIntError) -> Self {
+        PresentError::Side(err)
+    }
+}
+
+impl core::str::FromStr for Present {
+    type Err = PresentError;
 
+    /// Read a present's dimensions, like `2x3x4`. Presents so big that
+    /// the paper or ribbon for them can't be measured are an error
+    ///
+    /// ```
+    /// # use aoc2015::day2::*;
+    /// let present = "2x3x4".parse::<Present>().unwrap();
+    /// assert_eq!((present.l, present.w, present.h), (2, 3, 4));
+    ///
+    /// assert!(matches!("2x3".parse::<Present>(), Err(PresentError::Dimensions(2))));
+    /// assert!(matches!("2x3xfour".parse::<Present>(), Err(PresentError::Side(_))));
+    /// assert!(matches!("4000000000x4000000000x1".parse::<Present>(), Err(PresentError::TooBig)));
+    /// assert!(matches!("1626x1626x1626".parse::<Present>(), Err(PresentError::TooBig)));
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let bits = s.trim().split('x').collect::<Vec<_>>();
-        Ok(Self {
-            l: bits[0].parse()?,
-            w: bits[1].parse()?,
-            h: bits[2].parse()?,
-        })
+        let [l, w, h] = bits[..] else {
+            return Err(PresentError::Dimensions(bits.len()));
+        };
+
+        let present = Self {
+            l: l.parse()?,
+            w: w.parse()?,
+            h: h.parse()?,
+        };
+
+        match present.fits() {
+            true => Ok(present),
+            false => Err(PresentError::TooBig),
+        }
     }
 }
 
 impl Present {
+    /// Whether the paper and ribbon for the present, and everything
+    /// they're worked out from, can all be measured in a `u32`. Every
+    /// present that's been parsed does
+    ///
+    /// ```
+    /// # use aoc2015::day2::*;
+    /// assert!(Present { l: 2, w: 3, h: 4 }.fits());
+    /// assert!(!Present { l: 70000, w: 70000, h: 1 }.fits());
+    /// ```
+    pub fn fits(&self) -> bool {
+        let [l, w, h] = [self.l, self.w, self.h].map(u128::from);
+        let paper = 2 * (l * w + w * h + h * l) + (l * w).min(w * h).min(h * l);
+        let ribbon = 2 * (l + w + h - l.max(w).max(h)) + l * w * h;
+        paper <= u128::from(u32::MAX) && ribbon <= u128::from(u32::MAX)
+    }
+
     /// Find the total surface area of the present
     ///
     /// Examples
@@ -81,12 +161,12 @@ impl Present {
 #[cfg(test)]
 mod answers {
     use super::*;
-
-    static INPUT: &str = include_str!("./input/day2.txt");
+    use crate::input::bundled;
 
     #[test]
     pub fn problem1() {
-        let total = INPUT
+        let total = bundled(2)
+            .unwrap()
             .lines()
             .flat_map(|line| line.parse::<Present>())
             .fold(0, |accum, x| accum + x.total_area() + x.slack());
@@ -96,7 +176,8 @@ mod answers {
 
     #[test]
     pub fn problem2() {
-        let total = INPUT
+        let total = bundled(2)
+            .unwrap()
             .lines()
             .flat_map(|line| line.parse::<Present>())
             .fold(0, |accum, x| accum + x.ribbon_length() + x.bow_length());
@@ -104,3 +185,33 @@ mod answers {
         assert_eq!(total, 3812909);
     }
 }
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn ribbon_is_smallest_perimeter(l in 1..1000u32, w in 1..1000u32, h in 1..1000u32) {
+            let present = Present { l, w, h };
+            let perimeters = [2 * (l + w), 2 * (w + h), 2 * (l + h)];
+            prop_assert_eq!(present.ribbon_length(), *perimeters.iter().min().unwrap());
+        }
+
+        #[test]
+        fn orientation_doesnt_matter(l in 1..1000u32, w in 1..1000u32, h in 1..1000u32) {
+            let present = Present { l, w, h };
+            for turned in [Present { l: w, w: h, h: l }, Present { l: h, w: l, h: w }, Present { l: w, w: l, h }] {
+                prop_assert_eq!(turned.total_area() + turned.slack(), present.total_area() + present.slack());
+                prop_assert_eq!(turned.ribbon_length() + turned.bow_length(), present.ribbon_length() + present.bow_length());
+            }
+        }
+
+        #[test]
+        fn parses_what_it_prints(l in 1..1000u32, w in 1..1000u32, h in 1..1000u32) {
+            let present = format!("{l}x{w}x{h}").parse::<Present>().unwrap();
+            prop_assert_eq!((present.l, present.w, present.h), (l, w, h));
+        }
+    }
+}
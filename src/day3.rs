@@ -1,6 +1,16 @@
 //! Day 3 - Perfectly Spherical Houses in a Vacuum
 
-use std::collections::HashMap;
+use crate::grid::SparseGrid;
+
+mod agents;
+mod lattice;
+mod render;
+mod route;
+
+pub use agents::*;
+pub use lattice::*;
+pub use render::*;
+pub use route::*;
 
 /// Given a set of instructions from elf HQ, returns the table
 /// of visits to each location and the number of stops that we
@@ -8,56 +18,65 @@ use std::collections::HashMap;
 ///
 /// ```
 /// # use aoc2015::day3::*;
-/// let visits = collect_visits(">".chars());
+/// let visits = collect_visits(">".chars()).unwrap();
 /// assert_eq!(visits.len(), 2);
+///
+/// assert_eq!(collect_visits(">e".chars()), Err(UnknownMove('e')));
 /// ```
-pub fn collect_visits(moves: impl Iterator<Item=char>) -> HashMap<(isize, isize), u32> {
-    let mut visits = HashMap::new();
-    let start = (0, 0);
-    visits.insert(start, 1);
-
-    moves
-        .fold(start, |mut loc, next| {
-            match next {
-                '<' => loc = (loc.0 - 1, loc.1),
-                '>' => loc = (loc.0 + 1, loc.1),
-                '^' => loc = (loc.0, loc.1 + 1),
-                'v' => loc = (loc.0, loc.1 - 1),
-                err => panic!("Don't know what to do with a {err}"),
-            }
-
-            *visits.entry(loc).or_insert(0) += 1;
-            loc
-        });
-
-    visits
+pub fn collect_visits(moves: impl Iterator<Item=char>) -> Result<SparseGrid<u32>, UnknownMove> {
+    collect_visits_on::<Square4>(moves).map(SparseGrid::from)
 }
 
 #[cfg(test)]
 mod answers {
     use super::*;
-    use std::collections::HashSet;
-
-    static INPUT: &str = include_str!("./input/day3.txt");
+    use crate::input::bundled;
 
     #[test]
     pub fn problem1() {
-        let visits = collect_visits(INPUT.trim().chars());
+        let visits = collect_visits(bundled(3).unwrap().chars()).unwrap();
         assert_eq!(visits.len(), 2572);
     }
 
     #[test]
     pub fn problem2() {
-        let santa_moves = INPUT.trim().chars().step_by(2);
-        let robot_moves = INPUT.trim().chars().skip(1).step_by(2);
+        let deliveries = deliver(2, Turns::RoundRobin, bundled(3).unwrap().chars()).unwrap();
+        assert_eq!(deliveries.houses(), 2631);
+        assert_eq!(deliveries.visited().len(), 2631);
+    }
+}
 
-        let santa_visits = collect_visits(santa_moves);
-        let robot_visits = collect_visits(robot_moves);
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
 
-        let mut all_houses = HashSet::<(isize, isize)>::new();
-        santa_visits.keys().collect_into(&mut all_houses);
-        robot_visits.keys().collect_into(&mut all_houses);
+    proptest! {
+        #[test]
+        fn visits_add_up_to_moves(moves in "[<>^v]{0,500}") {
+            let visits = collect_visits(moves.chars()).unwrap();
+            prop_assert_eq!(visits.values().sum::<u32>() as usize, moves.len() + 1);
+        }
 
-        assert_eq!(all_houses.len(), 2631);
+        #[test]
+        fn one_agent_visits_the_same_houses(moves in "[<>^v]{0,500}") {
+            let visits = collect_visits(moves.chars()).unwrap();
+            let deliveries = deliver(1, Turns::RoundRobin, moves.chars()).unwrap();
+            prop_assert_eq!(&deliveries.combined, &visits);
+
+            let route = Route::follow(moves.chars()).unwrap();
+            prop_assert_eq!(route.loops().len(), moves.len() + 1 - visits.len());
+        }
+
+        #[test]
+        fn agents_share_the_work(moves in "[<>^v]{0,500}", agents in 1..5usize) {
+            let deliveries = deliver(agents, Turns::RoundRobin, moves.chars()).unwrap();
+            let total = deliveries.combined.values().sum::<u32>() as usize;
+            prop_assert_eq!(total, moves.len() + agents);
+            for (house, &visits) in deliveries.combined.iter() {
+                let per_agent = deliveries.per_agent.iter().filter_map(|agent| agent.get(house)).sum::<u32>();
+                prop_assert_eq!(per_agent, visits);
+            }
+        }
     }
 }
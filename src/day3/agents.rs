@@ -0,0 +1,212 @@
+//! Deliveries made by several agents sharing one set of instructions
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::grid::{Pos, SparseGrid};
+
+use super::{Lattice, Square4, UnknownMove};
+
+/// How the moves from elf HQ are handed out between agents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turns<'a> {
+    /// Agents take turns in order, one move each
+    RoundRobin,
+
+    /// Move `n` is made by the agent with the `n`th tag
+    Tagged(&'a [usize]),
+}
+
+impl Turns<'_> {
+    /// Figure out which agent makes the move at `index`
+    fn agent_for(&self, index: usize, agents: usize) -> Result<usize, DeliveryError> {
+        let agent = match self {
+            Turns::RoundRobin => index % agents,
+            Turns::Tagged(tags) => *tags.get(index).ok_or(DeliveryError::Untagged(index))?,
+        };
+
+        match agent < agents {
+            true => Ok(agent),
+            false => Err(DeliveryError::NoSuchAgent { index, agent, agents }),
+        }
+    }
+}
+
+/// Why the moves couldn't be handed out and followed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryError {
+    /// There was nobody to make the moves
+    NoAgents,
+
+    /// The move at this index didn't have a tag
+    Untagged(usize),
+
+    /// A move was tagged for an agent that isn't there
+    NoSuchAgent { index: usize, agent: usize, agents: usize },
+
+    /// One of the moves wasn't an arrow
+    Move(UnknownMove),
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeliveryError::NoAgents => write!(f, "Somebody has to deliver the presents"),
+            DeliveryError::Untagged(index) => write!(f, "Move {index} doesn't say which agent makes it"),
+            DeliveryError::NoSuchAgent { index, agent, agents } => {
+                write!(f, "Move {index} was assigned to agent {agent}, but there are only {agents}")
+            }
+            DeliveryError::Move(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for DeliveryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeliveryError::Move(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<UnknownMove> for DeliveryError {
+    fn from(err: UnknownMove) -> Self {
+        DeliveryError::Move(err)
+    }
+}
+
+/// The houses visited by a group of agents
+#[derive(Debug, Default)]
+pub struct Deliveries {
+    /// The table of visits made by each agent
+    pub per_agent: Vec<SparseGrid<u32>>,
+
+    /// The table of visits made by everyone
+    pub combined: SparseGrid<u32>,
+
+    /// The agent that got to each house first
+    pub first_visitor: SparseGrid<usize>,
+}
+
+impl Deliveries {
+    /// The number of houses that got at least one present
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// let deliveries = deliver(2, Turns::RoundRobin, "^v^v^v^v^v".chars()).unwrap();
+    /// assert_eq!(deliveries.houses(), 11);
+    /// ```
+    pub fn houses(&self) -> usize {
+        self.combined.len()
+    }
+
+    /// Every house that got at least one present, gathered from each
+    /// agent's own visits rather than the combined tally
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// # use aoc2015::grid::Pos;
+    /// let deliveries = deliver(2, Turns::RoundRobin, "^>v<".chars()).unwrap();
+    /// let visited = deliveries.visited();
+    /// assert_eq!(visited.len(), deliveries.houses());
+    /// assert!(visited.contains(&Pos::new(1, 0)));
+    /// ```
+    pub fn visited(&self) -> HashSet<Pos> {
+        let mut houses = HashSet::new();
+        for visits in &self.per_agent {
+            #[cfg(feature = "nightly")]
+            visits.positions().collect_into(&mut houses);
+            #[cfg(not(feature = "nightly"))]
+            houses.extend(visits.positions());
+        }
+        houses
+    }
+
+    /// The number of agents that stopped at a given house
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// # use aoc2015::grid::Pos;
+    /// let deliveries = deliver(2, Turns::RoundRobin, "^>v<".chars()).unwrap();
+    /// assert_eq!(deliveries.visitors(Pos::ORIGIN), 2);
+    /// assert_eq!(deliveries.visitors(Pos::new(0, 1)), 1);
+    /// assert_eq!(deliveries.visitors(Pos::new(5, 5)), 0);
+    /// ```
+    pub fn visitors(&self, house: Pos) -> usize {
+        self.per_agent
+            .iter()
+            .filter(|visits| visits.contains(house))
+            .count()
+    }
+
+    /// The number of houses that were visited by more than one agent
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// let deliveries = deliver(2, Turns::RoundRobin, "^>v<".chars()).unwrap();
+    /// assert_eq!(deliveries.overlaps(), 1);
+    /// ```
+    pub fn overlaps(&self) -> usize {
+        self.combined
+            .positions()
+            .filter(|&house| self.visitors(house) > 1)
+            .count()
+    }
+}
+
+/// Given a number of agents, a way of handing out turns, and a set of
+/// instructions from elf HQ, returns the visits made by each of the
+/// agents. Everybody starts out at the same house, which counts as a
+/// visit by every agent, and the first agent is credited with getting
+/// there first. It's an error if there are no agents, or if a move
+/// isn't an arrow or can't be handed to one of the agents.
+///
+/// ```
+/// # use aoc2015::day3::*;
+/// # use aoc2015::grid::Pos;
+/// let deliveries = deliver(2, Turns::RoundRobin, "^v".chars()).unwrap();
+/// assert_eq!(deliveries.houses(), 3);
+/// assert_eq!(deliveries.combined[Pos::ORIGIN], 2);
+/// assert_eq!(deliveries.first_visitor[Pos::new(0, -1)], 1);
+///
+/// let deliveries = deliver(2, Turns::Tagged(&[1, 1, 0]), ">><".chars()).unwrap();
+/// assert_eq!(deliveries.per_agent[0].len(), 2);
+/// assert_eq!(deliveries.per_agent[1].len(), 3);
+/// assert_eq!(deliveries.first_visitor[Pos::new(1, 0)], 1);
+///
+/// assert_eq!(deliver(0, Turns::RoundRobin, "^".chars()).unwrap_err(), DeliveryError::NoAgents);
+/// assert_eq!(deliver(2, Turns::Tagged(&[0]), "^^".chars()).unwrap_err(), DeliveryError::Untagged(1));
+/// assert_eq!(
+///     deliver(2, Turns::Tagged(&[2]), "^".chars()).unwrap_err(),
+///     DeliveryError::NoSuchAgent { index: 0, agent: 2, agents: 2 },
+/// );
+/// ```
+pub fn deliver(agents: usize, turns: Turns, moves: impl Iterator<Item=char>) -> Result<Deliveries, DeliveryError> {
+    if agents == 0 {
+        return Err(DeliveryError::NoAgents);
+    }
+
+    let start = Pos::ORIGIN;
+    let mut deliveries = Deliveries {
+        per_agent: vec![SparseGrid::from_iter([(start, 1)]); agents],
+        combined: SparseGrid::from_iter([(start, agents as u32)]),
+        first_visitor: SparseGrid::from_iter([(start, 0)]),
+    };
+
+    moves
+        .enumerate()
+        .try_fold(vec![start; agents], |mut locs, (index, next)| -> Result<_, DeliveryError> {
+            let agent = turns.agent_for(index, agents)?;
+            let loc = Square4::step(locs[agent], next).ok_or(UnknownMove(next))?;
+
+            *deliveries.per_agent[agent].entry(loc).or_insert(0) += 1;
+            *deliveries.combined.entry(loc).or_insert(0) += 1;
+            deliveries.first_visitor.entry(loc).or_insert(agent);
+
+            locs[agent] = loc;
+            Ok(locs)
+        })?;
+
+    Ok(deliveries)
+}
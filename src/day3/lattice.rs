@@ -0,0 +1,187 @@
+//! The shapes of neighbourhood that the walker knows how to get
+//! around, each with its own set of moves
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+use crate::grid::{Dir, Pos};
+
+/// A regular arrangement of houses, and the moves between them
+pub trait Lattice {
+    /// The location of a house
+    type Coord: Copy + Debug + Eq + Hash;
+
+    /// Where every walk starts
+    const ORIGIN: Self::Coord;
+
+    /// The house reached by making a single move from `loc`, if the
+    /// move makes any sense here
+    fn step(loc: Self::Coord, next: char) -> Option<Self::Coord>;
+}
+
+/// Square blocks where you can only move north, south, east or west,
+/// with the arrows. This is the lattice elf HQ uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Square4;
+
+impl Lattice for Square4 {
+    type Coord = Pos;
+
+    const ORIGIN: Pos = Pos::ORIGIN;
+
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// # use aoc2015::grid::Pos;
+    /// assert_eq!(Square4::step(Pos::ORIGIN, '<'), Some(Pos::new(-1, 0)));
+    /// assert_eq!(Square4::step(Pos::ORIGIN, 'w'), None);
+    /// assert_eq!(Square4::step(Pos::ORIGIN, '9'), None);
+    /// ```
+    fn step(loc: Pos, next: char) -> Option<Pos> {
+        Dir::from_arrow(next).ok().map(|dir| loc.step(dir))
+    }
+}
+
+/// Square blocks where you can also cut across diagonally. Besides the
+/// arrows, the corners of a numeric keypad (`7`, `9`, `1` and `3`) move
+/// northwest, northeast, southwest and southeast respectively
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Square8;
+
+impl Lattice for Square8 {
+    type Coord = Pos;
+
+    const ORIGIN: Pos = Pos::ORIGIN;
+
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// # use aoc2015::grid::Pos;
+    /// assert_eq!(Square8::step(Pos::ORIGIN, '>'), Some(Pos::new(1, 0)));
+    /// assert_eq!(Square8::step(Pos::ORIGIN, '9'), Some(Pos::new(1, 1)));
+    /// assert_eq!(Square8::step(Pos::ORIGIN, '1'), Some(Pos::new(-1, -1)));
+    /// assert_eq!(Square8::step(Pos::ORIGIN, '5'), None);
+    /// ```
+    fn step(loc: Pos, next: char) -> Option<Pos> {
+        let offset = match next {
+            '7' => Pos::new(-1, 1),
+            '9' => Pos::new(1, 1),
+            '1' => Pos::new(-1, -1),
+            '3' => Pos::new(1, -1),
+            _ => return Square4::step(loc, next),
+        };
+
+        Some(loc + offset)
+    }
+}
+
+/// The location of a hexagonal block, in axial coordinates
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// Hexagonal blocks with six neighbours apiece. The moves are the keys
+/// surrounding `s` on a keyboard: `w` and `e` go northwest and
+/// northeast, `a` and `d` go west and east, and `z` and `x` go
+/// southwest and southeast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexAxial;
+
+impl Lattice for HexAxial {
+    type Coord = Hex;
+
+    const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// let there = "wd".chars().try_fold(Hex::default(), HexAxial::step);
+    /// assert_eq!(there, Some(Hex { q: 1, r: -1 }));
+    /// assert_eq!(there, HexAxial::step(Hex::default(), 'e'));
+    /// assert_eq!(HexAxial::step(Hex::default(), '^'), None);
+    /// ```
+    fn step(loc: Hex, next: char) -> Option<Hex> {
+        let (dq, dr) = match next {
+            'w' => (0, -1),
+            'e' => (1, -1),
+            'a' => (-1, 0),
+            'd' => (1, 0),
+            'z' => (-1, 1),
+            'x' => (0, 1),
+            _ => return None,
+        };
+
+        Some(Hex { q: loc.q + dq, r: loc.r + dr })
+    }
+}
+
+/// The location of a cubic block
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// Stacks of cubic blocks. The arrows move around within a floor,
+/// and `+` and `-` go up and down a floor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cubic;
+
+impl Lattice for Cubic {
+    type Coord = Pos3;
+
+    const ORIGIN: Pos3 = Pos3 { x: 0, y: 0, z: 0 };
+
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert_eq!(Cubic::step(Pos3::default(), '+'), Some(Pos3 { x: 0, y: 0, z: 1 }));
+    /// assert_eq!(Cubic::step(Pos3::default(), '^'), Some(Pos3 { x: 0, y: 1, z: 0 }));
+    /// ```
+    fn step(loc: Pos3, next: char) -> Option<Pos3> {
+        match next {
+            '+' => Some(Pos3 { z: loc.z + 1, ..loc }),
+            '-' => Some(Pos3 { z: loc.z - 1, ..loc }),
+            _ => Square4::step(Pos::new(loc.x, loc.y), next).map(|pos| Pos3 { x: pos.x, y: pos.y, z: loc.z }),
+        }
+    }
+}
+
+/// A move that doesn't make any sense on the lattice being walked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownMove(pub char);
+
+impl fmt::Display for UnknownMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Don't know what to do with a {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownMove {}
+
+/// Given a set of instructions for a lattice, returns the table of
+/// visits to each location, or the first move that the lattice doesn't
+/// understand
+///
+/// ```
+/// # use aoc2015::day3::*;
+/// assert_eq!(collect_visits_on::<Square4>("^>v<".chars()).unwrap().len(), 4);
+/// assert_eq!(collect_visits_on::<Square8>("^31".chars()).unwrap().len(), 4);
+/// assert_eq!(collect_visits_on::<HexAxial>("wdx".chars()).unwrap().len(), 4);
+/// assert_eq!(collect_visits_on::<Cubic>("+^-v".chars()).unwrap().len(), 4);
+/// assert_eq!(collect_visits_on::<Square4>("^n".chars()), Err(UnknownMove('n')));
+/// ```
+pub fn collect_visits_on<L: Lattice>(mut moves: impl Iterator<Item=char>) -> Result<HashMap<L::Coord, u32>, UnknownMove> {
+    let mut visits = HashMap::new();
+    let start = L::ORIGIN;
+    visits.insert(start, 1);
+
+    moves
+        .try_fold(start, |loc, next| {
+            let loc = L::step(loc, next).ok_or(UnknownMove(next))?;
+            *visits.entry(loc).or_insert(0) += 1;
+            Ok(loc)
+        })?;
+
+    Ok(visits)
+}
@@ -0,0 +1,121 @@
+//! Pictures of the delivery map, for eyeballing the routes
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::grid::{Bounds, Pos, SparseGrid};
+
+/// Glyphs for houses from least to most visited. The first is used for
+/// houses that didn't get any presents
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+/// The glyph marking the house everybody started from
+pub const ORIGIN_GLYPH: char = 'S';
+
+/// A ramp without enough glyphs to tell empty houses from visited ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RampError {
+    pub glyphs: usize,
+}
+
+impl fmt::Display for RampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A ramp needs at least two glyphs, but this one has {}", self.glyphs)
+    }
+}
+
+impl std::error::Error for RampError {}
+
+/// Pick which of `levels` buckets a visit count falls into, spreading
+/// 1..=max evenly over them
+fn bucket(count: u32, max: u32, levels: usize) -> usize {
+    if max <= 1 || levels <= 1 {
+        return levels.saturating_sub(1);
+    }
+
+    (count.saturating_sub(1) as usize * (levels - 1)) / (max - 1) as usize
+}
+
+/// The area covering every visited house, and always the origin
+fn area(visits: &SparseGrid<u32>) -> Bounds {
+    Bounds::of(visits.positions().chain([Pos::ORIGIN])).expect("Always has the origin")
+}
+
+/// Draw the visits as a heat map, north at the top, using glyphs from
+/// `ramp` for the number of times each house was visited. The ramp needs
+/// a glyph for empty houses and at least one for visited ones
+///
+/// ```
+/// # use aoc2015::day3::*;
+/// let visits = collect_visits("^>v<>>".chars()).unwrap();
+/// assert_eq!(render_ascii(&visits, DEFAULT_RAMP).unwrap(), ".. \nS@.\n");
+///
+/// let visits = collect_visits(">>>".chars()).unwrap();
+/// assert_eq!(render_ascii(&visits, " x").unwrap(), "Sxxx\n");
+/// assert_eq!(render_ascii(&visits, "x"), Err(RampError { glyphs: 1 }));
+/// ```
+pub fn render_ascii(visits: &SparseGrid<u32>, ramp: &str) -> Result<String, RampError> {
+    let glyphs = ramp.chars().collect::<Vec<_>>();
+    let Some((&empty, heat)) = glyphs.split_first().filter(|(_, heat)| !heat.is_empty()) else {
+        return Err(RampError { glyphs: glyphs.len() });
+    };
+
+    let bounds = area(visits);
+    let max = visits.values().copied().max().unwrap_or(0);
+
+    let mut picture = String::with_capacity((bounds.width() + 1) * bounds.height());
+    for y in (bounds.min.y..=bounds.max.y).rev() {
+        let row = (bounds.min.x..=bounds.max.x).map(|x| {
+            let pos = Pos::new(x, y);
+            match visits.get(pos) {
+                _ if pos == Pos::ORIGIN => ORIGIN_GLYPH,
+                Some(&count) => heat[bucket(count, max, heat.len())],
+                None => empty,
+            }
+        });
+
+        picture.extend(row);
+        picture.push('\n');
+    }
+
+    Ok(picture)
+}
+
+/// Write the visits out as a binary greyscale PGM image, north at the
+/// top. Brighter pixels were visited more often, and the origin is
+/// pure white.
+///
+/// ```
+/// # use aoc2015::day3::*;
+/// let visits = collect_visits(">>".chars()).unwrap();
+/// let mut image = Vec::new();
+/// write_pgm(&visits, &mut image).unwrap();
+/// assert_eq!(image, b"P5\n3 1\n255\n\xff\xfe\xfe");
+/// ```
+pub fn write_pgm(visits: &SparseGrid<u32>, out: &mut impl Write) -> io::Result<()> {
+    // Leave the very brightest level for the origin, and keep the
+    // dimmest visited houses distinguishable from the background
+    const DIMMEST: u8 = 64;
+    const LEVELS: usize = (u8::MAX - DIMMEST) as usize;
+
+    let bounds = area(visits);
+    let max = visits.values().copied().max().unwrap_or(0);
+
+    write!(out, "P5\n{} {}\n{}\n", bounds.width(), bounds.height(), u8::MAX)?;
+    for y in (bounds.min.y..=bounds.max.y).rev() {
+        let row = (bounds.min.x..=bounds.max.x)
+            .map(|x| {
+                let pos = Pos::new(x, y);
+                match visits.get(pos) {
+                    _ if pos == Pos::ORIGIN => u8::MAX,
+                    Some(&count) => DIMMEST + bucket(count, max, LEVELS) as u8,
+                    None => 0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        out.write_all(&row)?;
+    }
+
+    Ok(())
+}
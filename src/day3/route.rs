@@ -0,0 +1,177 @@
+//! The ordered path taken through the neighbourhood, and some
+//! statistics about it
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::grid::{Dir, Pos};
+
+use super::UnknownMove;
+
+/// Every house passed through, in order, following a set of
+/// instructions from elf HQ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    moves: Vec<Dir>,
+    path: Vec<Pos>,
+}
+
+impl Route {
+    /// Follow the instructions from the origin, as long as every move
+    /// is an arrow
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// # use aoc2015::grid::Pos;
+    /// let route = Route::follow("^>".chars()).unwrap();
+    /// assert_eq!(route.path(), &[Pos::ORIGIN, Pos::new(0, 1), Pos::new(1, 1)]);
+    ///
+    /// assert_eq!(Route::follow("^N".chars()), Err(UnknownMove('N')));
+    /// ```
+    pub fn follow(moves: impl Iterator<Item=char>) -> Result<Self, UnknownMove> {
+        let moves = moves
+            .map(|next| Dir::from_arrow(next).map_err(|_| UnknownMove(next)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let path = std::iter::once(Pos::ORIGIN)
+            .chain(moves.iter().scan(Pos::ORIGIN, |loc, &dir| {
+                *loc = loc.step(dir);
+                Some(*loc)
+            }))
+            .collect();
+
+        Ok(Self { moves, path })
+    }
+
+    /// The houses along the route, starting with the origin
+    pub fn path(&self) -> &[Pos] {
+        &self.path
+    }
+
+    /// The total number of steps taken
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert_eq!(Route::follow("^>v<".chars()).unwrap().distance(), 4);
+    /// assert_eq!(Route::follow("".chars()).unwrap().distance(), 0);
+    /// ```
+    pub fn distance(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// The furthest the route ever gets from the origin, as the
+    /// crow drives
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert_eq!(Route::follow("^^>>vvvv<".chars()).unwrap().max_distance(), 4);
+    /// ```
+    pub fn max_distance(&self) -> usize {
+        self.path
+            .iter()
+            .map(|pos| pos.manhattan(Pos::ORIGIN))
+            .max()
+            .expect("There's always the origin")
+    }
+
+    /// The step on which we first arrive at a house that we've already
+    /// been to, if we ever do
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert_eq!(Route::follow("^>v<".chars()).unwrap().first_revisit(), Some(4));
+    /// assert_eq!(Route::follow(">>^^<".chars()).unwrap().first_revisit(), None);
+    /// ```
+    pub fn first_revisit(&self) -> Option<usize> {
+        self.loops().first().map(|found| found.end)
+    }
+
+    /// The most steps taken in a row without changing direction
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert_eq!(Route::follow("^^>>>v".chars()).unwrap().longest_straight(), 3);
+    /// assert_eq!(Route::follow("".chars()).unwrap().longest_straight(), 0);
+    /// ```
+    pub fn longest_straight(&self) -> usize {
+        self.moves
+            .iter()
+            .scan((None, 0), |(prev, run), &dir| {
+                *run = if *prev == Some(dir) { *run + 1 } else { 1 };
+                *prev = Some(dir);
+                Some(*run)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Every closed loop in the route, as the range of steps between
+    /// leaving a house and next coming back to it, in the order that
+    /// they close
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// let route = Route::follow("^>v<^".chars()).unwrap();
+    /// assert_eq!(route.loops(), vec![0..4, 1..5]);
+    /// ```
+    pub fn loops(&self) -> Vec<Range<usize>> {
+        let mut last_seen = HashMap::new();
+
+        self.path
+            .iter()
+            .enumerate()
+            .filter_map(|(step, &pos)| last_seen.insert(pos, step).map(|start| start..step))
+            .collect()
+    }
+
+    /// Whether the route finishes back where it started
+    ///
+    /// ```
+    /// # use aoc2015::day3::*;
+    /// assert!(Route::follow("^>v<".chars()).unwrap().is_closed());
+    /// assert!(!Route::follow("^>v".chars()).unwrap().is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.path.last() == Some(&Pos::ORIGIN)
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+
+    fn route() -> Route {
+        Route::follow(bundled(3).unwrap().chars()).unwrap()
+    }
+
+    #[test]
+    pub fn distance() {
+        assert_eq!(route().distance(), 8192);
+    }
+
+    #[test]
+    pub fn max_distance() {
+        assert_eq!(route().max_distance(), 117);
+    }
+
+    #[test]
+    pub fn first_revisit() {
+        assert_eq!(route().first_revisit(), Some(12));
+    }
+
+    #[test]
+    pub fn longest_straight() {
+        assert_eq!(route().longest_straight(), 7);
+    }
+
+    #[test]
+    pub fn loops() {
+        // Every stop at a house we'd already seen closes a loop
+        let route = route();
+        let loops = route.loops();
+        assert_eq!(loops.len(), route.distance() + 1 - 2572);
+        assert_eq!(loops[0], 10..12);
+        assert!(!route.is_closed());
+    }
+}
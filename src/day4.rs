@@ -37,14 +37,15 @@ pub fn mine_advent_coin_v2(secret_key: &str) -> u32 {
 #[cfg(test)]
 mod answers {
     use super::*;
+    use crate::input::bundled;
 
     #[test]
     pub fn problem1() {
-        assert_eq!(mine_advent_coin_v1("iwrupvqb"), 346386);
+        assert_eq!(mine_advent_coin_v1(bundled(4).unwrap()), 346386);
     }
 
     #[test]
     pub fn problem2() {
-        assert_eq!(mine_advent_coin_v2("iwrupvqb"), 9958218);
+        assert_eq!(mine_advent_coin_v2(bundled(4).unwrap()), 9958218);
     }
 }
@@ -1,6 +1,20 @@
 //! Day 5 - Doesn't He Have Intern-Elves For This?
 
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+
+use crate::predicates;
+
+mod explain;
+mod generate;
+mod rules;
+mod scan;
+mod unicode;
+
+pub use explain::*;
+pub use generate::*;
+pub use rules::*;
+pub use scan::*;
+pub use unicode::*;
 
 /// Determine if a string has at least three (not necessarily distinct)
 /// vowels
@@ -28,12 +42,12 @@ pub fn has_three_vowels(s: &str) -> bool {
 /// assert!(!has_doubled_letter("abcde"));
 /// ```
 pub fn has_doubled_letter(s: &str) -> bool {
-    s.as_bytes()
-        .windows(2)
-        .filter(|&w| w[0] == w[1])
-        .count() > 0
+    predicates::has_doubled_letter(s.as_bytes())
 }
 
+/// The pairs of letters that make a string naughty under the v1 rules
+pub const FORBIDDEN_SUBSTRINGS: [[u8; 2]; 4] = [*b"ab", *b"cd", *b"pq", *b"xy"];
+
 /// Determine if a string has any of the forbidden substrings
 ///
 /// ```
@@ -45,18 +59,26 @@ pub fn has_doubled_letter(s: &str) -> bool {
 /// assert!(!has_forbidden_substring("ghijklm"));
 /// ```
 pub fn has_forbidden_substring(s: &str) -> bool {
-    s.as_bytes()
-        .windows(2)
-        .filter(|&w| {
-            (w[0] == b'a' && w[1] == b'b') ||
-            (w[0] == b'c' && w[1] == b'd') ||
-            (w[0] == b'p' && w[1] == b'q') ||
-            (w[0] == b'x' && w[1] == b'y')
-        })
-        .count() > 0
+    predicates::contains_any_pair(s.as_bytes(), &FORBIDDEN_SUBSTRINGS)
 }
 
-/// Determine if a string has at least one non-overlapping pair
+/// Whether a byte is padding, which the pair rule ignores at either end
+/// of a string. Only ASCII whitespace counts, so that it can be spotted
+/// a byte at a time
+fn is_padding(byte: u8) -> bool {
+    byte.is_ascii_whitespace()
+}
+
+/// The bytes of a string without the padding at either end, and how
+/// many bytes of padding came before them
+fn without_padding(s: &[u8]) -> (usize, &[u8]) {
+    let leading = s.iter().take_while(|&&byte| is_padding(byte)).count();
+    let trailing = s[leading..].iter().rev().take_while(|&&byte| is_padding(byte)).count();
+    (leading, &s[leading..s.len() - trailing])
+}
+
+/// Determine if a string has at least one non-overlapping pair, leaving
+/// out any padding at either end
 ///
 /// ```
 /// # use aoc2015::day5::*;
@@ -65,14 +87,16 @@ pub fn has_forbidden_substring(s: &str) -> bool {
 /// assert!(has_nonoverlapping_pair("xxyxx"));
 /// assert!(has_nonoverlapping_pair("uurcxstgmygtbstg"));
 /// assert!(!has_nonoverlapping_pair("ieodomkazucvgmuy"));
+/// assert!(!has_nonoverlapping_pair("  x  "));
+/// assert!(has_nonoverlapping_pair("x    x"));
 /// ```
 pub fn has_nonoverlapping_pair(s: &str) -> bool {
     // Iterate over every window. If we've seen this pair before
     // *and* it wasn't at the position immediately before this one,
     // we're good
-    let mut seen_pairs = HashMap::new();
+    let mut seen_pairs = BTreeMap::new();
 
-    for (current_position, pair) in s.trim().as_bytes().windows(2).enumerate() {
+    for (current_position, pair) in without_padding(s.as_bytes()).1.windows(2).enumerate() {
         let first_position = seen_pairs.entry(pair).or_insert(current_position);
 
         // If this isn't the first time we've seen it, and the last time we saw it wasn't
@@ -128,12 +152,12 @@ pub fn is_nice_v2(s: &str) -> bool {
 #[cfg(test)]
 mod answers {
     use super::*;
-
-    static INPUT: &str = include_str!("./input/day5.txt");
+    use crate::input::bundled;
 
     #[test]
     pub fn problem1() {
-        let nice_strings = INPUT
+        let nice_strings = bundled(5)
+            .unwrap()
             .lines()
             .filter(|line| is_nice_v1(line))
             .count();
@@ -143,7 +167,8 @@ mod answers {
 
     #[test]
     pub fn problem2() {
-        let nice_strings = INPUT
+        let nice_strings = bundled(5)
+            .unwrap()
             .lines()
             .filter(|line| is_nice_v2(line))
             .count();
@@ -151,3 +176,62 @@ mod answers {
         assert_eq!(nice_strings, 55);
     }
 }
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Mostly letters that the rules care about, so that plenty of the
+    // strings turn out nice
+    const STRINGS: &str = "[aeiouabcdpqxyz]{0,20}";
+
+    // Few enough letters that pairs turn up, with whitespace in between
+    // as well as around them
+    const PADDED: &str = "[axy \t]{0,12}";
+    const PADDING: &str = "[ \t\r\u{c}]{0,4}";
+
+    proptest! {
+        #[test]
+        fn scanner_agrees_with_predicates(s in STRINGS) {
+            prop_assert_eq!(scan(&s), (is_nice_v1(&s), is_nice_v2(&s)));
+        }
+
+        #[test]
+        fn scanner_agrees_on_padding(before in PADDING, s in PADDED, after in PADDING) {
+            let s = format!("{before}{s}{after}");
+            prop_assert_eq!(scan(&s), (is_nice_v1(&s), is_nice_v2(&s)));
+        }
+
+        #[test]
+        fn rules_agree_with_predicates(s in STRINGS) {
+            prop_assert_eq!(rules_v1().matches(&s), is_nice_v1(&s));
+            prop_assert_eq!(rules_v2().matches(&s), is_nice_v2(&s));
+        }
+
+        #[test]
+        fn explanations_agree_with_predicates(s in "[a-z\u{e9}\u{2082} ]{0,12}") {
+            let verdict = classify(&s);
+            prop_assert_eq!(verdict.is_nice_v1(), is_nice_v1(&s));
+            prop_assert_eq!(verdict.is_nice_v2(), is_nice_v2(&s));
+            prop_assert_eq!(verdict.to_string().lines().count(), 6);
+        }
+
+        #[test]
+        fn every_unit_agrees_on_ascii(s in STRINGS) {
+            for options in [Options::chars(ENGLISH_VOWELS), Options::graphemes(ENGLISH_VOWELS)] {
+                prop_assert_eq!(is_nice_v1_with(&s, &options), is_nice_v1(&s));
+                prop_assert_eq!(is_nice_v2_with(&s, &options), is_nice_v2(&s));
+            }
+        }
+
+        #[test]
+        fn every_unit_agrees_on_padding(before in PADDING, s in PADDED, after in PADDING) {
+            let s = format!("{before}{s}{after}");
+            for options in [Options::ascii(), Options::chars(ENGLISH_VOWELS), Options::graphemes(ENGLISH_VOWELS)] {
+                prop_assert_eq!(is_nice_v1_with(&s, &options), is_nice_v1(&s), "{:?}", options);
+                prop_assert_eq!(is_nice_v2_with(&s, &options), is_nice_v2(&s), "{:?}", options);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Explanations of why a string is naughty or nice
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::vec;
+use core::fmt;
+use core::ops::Range;
+
+use super::{without_padding, FORBIDDEN_SUBSTRINGS};
+
+/// One of the rules that nice strings are judged by
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Check {
+    ThreeVowels,
+    DoubledLetter,
+    NoForbiddenSubstring,
+    NonOverlappingPair,
+    Fenceposts,
+}
+
+impl Check {
+    /// The rules for the first version of nice strings
+    pub const V1: [Check; 3] = [Check::ThreeVowels, Check::DoubledLetter, Check::NoForbiddenSubstring];
+
+    /// The rules for the second version of nice strings
+    pub const V2: [Check; 2] = [Check::NonOverlappingPair, Check::Fenceposts];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Check::ThreeVowels => "at least three vowels",
+            Check::DoubledLetter => "a doubled letter",
+            Check::NoForbiddenSubstring => "none of ab, cd, pq or xy",
+            Check::NonOverlappingPair => "a pair appearing twice",
+            Check::Fenceposts => "a letter repeating with one between",
+        }
+    }
+}
+
+/// The outcome of a single rule, along with the parts of the string
+/// that decided it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub check: Check,
+    pub passed: bool,
+
+    /// Byte ranges of the string that the rule found. For the
+    /// forbidden substrings this is the reason it failed, for all of
+    /// the others it's the reason it passed
+    pub evidence: Vec<Range<usize>>,
+}
+
+/// How a string fares against every rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict<'a> {
+    pub input: &'a str,
+    pub findings: Vec<Finding>,
+}
+
+impl Verdict<'_> {
+    /// What a particular rule made of the string
+    pub fn finding(&self, check: Check) -> &Finding {
+        self.findings
+            .iter()
+            .find(|finding| finding.check == check)
+            .expect("Every check is always made")
+    }
+
+    fn passes(&self, checks: &[Check]) -> bool {
+        checks.iter().all(|&check| self.finding(check).passed)
+    }
+
+    /// Whether the string is nice under the first version of the rules
+    pub fn is_nice_v1(&self) -> bool {
+        self.passes(&Check::V1)
+    }
+
+    /// Whether the string is nice under the second version of the rules
+    pub fn is_nice_v2(&self) -> bool {
+        self.passes(&Check::V2)
+    }
+}
+
+impl fmt::Display for Verdict<'_> {
+    /// Show the string with each rule's evidence underlined beneath it
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Evidence is in bytes, and can start or end part way through a
+        // character, but the underlining needs to cover whole columns
+        let starts = self.input.char_indices().map(|(offset, _)| offset).collect::<Vec<_>>();
+        let column_containing = |offset: usize| starts.partition_point(|&start| start <= offset) - 1;
+        let column_after = |offset: usize| starts.partition_point(|&start| start < offset);
+
+        writeln!(f, "{:4} {}", "", self.input)?;
+        for finding in &self.findings {
+            let mut marks = vec![' '; starts.len()];
+            for span in &finding.evidence {
+                marks[column_containing(span.start)..column_after(span.end)].fill('^');
+            }
+
+            let status = if finding.passed { "pass" } else { "FAIL" };
+            let marks = marks.into_iter().collect::<String>();
+            writeln!(f, "{status:4} {marks}  {}", finding.check.description())?;
+        }
+
+        Ok(())
+    }
+}
+
+// Each rule looks at the bytes of the string, the same as the predicates
+// in `day5`, so that the explanation always agrees with them
+
+fn vowels(s: &str) -> Finding {
+    let evidence = s
+        .bytes()
+        .enumerate()
+        .filter(|(_, b)| b"aeiou".contains(b))
+        .map(|(offset, _)| offset..offset + 1)
+        .collect::<Vec<_>>();
+
+    Finding { check: Check::ThreeVowels, passed: evidence.len() >= 3, evidence }
+}
+
+fn first_window(s: &[u8], size: usize, found: impl Fn(&[u8]) -> bool) -> Vec<Range<usize>> {
+    s.windows(size)
+        .position(found)
+        .map(|offset| offset..offset + size)
+        .into_iter()
+        .collect()
+}
+
+fn doubled_letter(s: &str) -> Finding {
+    let evidence = first_window(s.as_bytes(), 2, |w| w[0] == w[1]);
+    Finding { check: Check::DoubledLetter, passed: !evidence.is_empty(), evidence }
+}
+
+fn forbidden_substring(s: &str) -> Finding {
+    let evidence = first_window(s.as_bytes(), 2, |w| FORBIDDEN_SUBSTRINGS.iter().any(|pair| w == pair));
+    Finding { check: Check::NoForbiddenSubstring, passed: evidence.is_empty(), evidence }
+}
+
+fn nonoverlapping_pair(s: &str) -> Finding {
+    // Like `has_nonoverlapping_pair`, this ignores padding at either
+    // end, but the evidence is still where it is in the whole string
+    let (leading, unpadded) = without_padding(s.as_bytes());
+    let mut seen_pairs = BTreeMap::new();
+    let evidence = unpadded
+        .windows(2)
+        .enumerate()
+        .find_map(|(current, pair)| {
+            let first = *seen_pairs.entry(pair).or_insert(current);
+            (current - first > 1).then_some([first..first + 2, current..current + 2])
+        })
+        .map(|spans| spans.map(|span| span.start + leading..span.end + leading))
+        .map(Vec::from)
+        .unwrap_or_default();
+
+    Finding { check: Check::NonOverlappingPair, passed: !evidence.is_empty(), evidence }
+}
+
+fn fenceposts(s: &str) -> Finding {
+    let evidence = first_window(s.as_bytes(), 3, |w| w[0] == w[2]);
+    Finding { check: Check::Fenceposts, passed: !evidence.is_empty(), evidence }
+}
+
+/// Check a string against every rule, keeping track of why each one
+/// passed or failed
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// let verdict = classify("haegwjzuvuyypxyu");
+/// assert!(!verdict.is_nice_v1());
+/// assert_eq!(verdict.finding(Check::ThreeVowels).evidence, vec![1..2, 2..3, 7..8, 9..10, 15..16]);
+/// assert_eq!(verdict.finding(Check::DoubledLetter).evidence, vec![10..12]);
+/// assert_eq!(verdict.finding(Check::NoForbiddenSubstring).evidence, vec![13..15]);
+///
+/// let verdict = classify("qjhvhtzxzqqjkmpb");
+/// assert!(verdict.is_nice_v2());
+/// assert_eq!(verdict.finding(Check::NonOverlappingPair).evidence, vec![0..2, 10..12]);
+/// assert_eq!(verdict.finding(Check::Fenceposts).evidence, vec![2..5]);
+///
+/// assert_eq!(classify("jchzalrnumimnmhp").to_string(), "     jchzalrnumimnmhp
+/// pass     ^   ^ ^       at least three vowels
+/// FAIL                   a doubled letter
+/// pass                   none of ab, cd, pq or xy
+/// FAIL                   a pair appearing twice
+/// pass          ^^^      a letter repeating with one between
+/// ");
+/// ```
+pub fn classify(s: &str) -> Verdict<'_> {
+    Verdict {
+        input: s,
+        findings: vec![
+            vowels(s),
+            doubled_letter(s),
+            forbidden_substring(s),
+            nonoverlapping_pair(s),
+            fenceposts(s),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::day5::{is_nice_v1, is_nice_v2};
+    use crate::input::bundled;
+
+    #[test]
+    pub fn problem1() {
+        let nice_strings = bundled(5)
+            .unwrap()
+            .lines()
+            .filter(|line| classify(line).is_nice_v1())
+            .count();
+
+        assert_eq!(nice_strings, 255);
+    }
+
+    #[test]
+    pub fn problem2() {
+        let nice_strings = bundled(5)
+            .unwrap()
+            .lines()
+            .filter(|line| classify(line).is_nice_v2())
+            .count();
+
+        assert_eq!(nice_strings, 55);
+    }
+
+    #[test]
+    pub fn multibyte_letters_line_up() {
+        // The rules look at bytes, so the two bytes that U+2082 ends in
+        // count as a doubled letter, and the underline covers the whole
+        // character they're in
+        let verdict = classify("a\u{2082}b");
+        assert_eq!(verdict.finding(Check::DoubledLetter).evidence, vec![2..4]);
+        assert_eq!(verdict.to_string(), "     a\u{2082}b
+FAIL ^    at least three vowels
+pass  ^   a doubled letter
+pass      none of ab, cd, pq or xy
+FAIL      a pair appearing twice
+FAIL      a letter repeating with one between
+");
+    }
+
+    #[test]
+    pub fn padding_is_ignored_for_pairs() {
+        let verdict = classify(" xyxy ");
+        assert_eq!(verdict.finding(Check::NonOverlappingPair).evidence, vec![1..3, 3..5]);
+        assert_eq!(verdict.is_nice_v2(), is_nice_v2(" xyxy "));
+    }
+
+    #[test]
+    pub fn agrees_with_predicates() {
+        for line in bundled(5).unwrap().lines() {
+            let verdict = classify(line);
+            assert_eq!(verdict.is_nice_v1(), is_nice_v1(line), "{verdict}");
+            assert_eq!(verdict.is_nice_v2(), is_nice_v2(line), "{verdict}");
+        }
+    }
+}
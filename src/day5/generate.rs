@@ -0,0 +1,436 @@
+//! Counting and making up nice (and naughty) strings
+//!
+//! Both versions of the rules can be checked by a machine that reads a
+//! string one letter at a time, so the number of strings that end up
+//! nice can be found by counting the ways to get from each state of the
+//! machine to the end, without ever writing the strings out. The same
+//! counts make it possible to pick a string uniformly at random, one
+//! letter at a time.
+//!
+//! The v1 machine only has a handful of states. The v2 machine has to
+//! remember every pair it has seen until it finds a repeat, so counting
+//! is only practical for small alphabets or short strings.
+//!
+//! Counting only keeps the states for one length at a time, so strings
+//! can be as long as you like. Picking a string keeps them for every
+//! length up to the one asked for.
+//!
+//! The counts are kept in a `u128`, which runs out once the number of
+//! nice or naughty strings passes 2^128 (at 28 letters for the whole
+//! alphabet). After that those strings can't be counted or picked, though
+//! the others still can.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::rng::Rng;
+
+/// Which set of rules to judge the strings by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    V1,
+    V2,
+}
+
+/// Where the rule checking machine has got to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum State {
+    V1 {
+        vowels: u8,
+        last: Option<u8>,
+        doubled: bool,
+    },
+
+    /// A v1 string that has hit a forbidden substring, which can never
+    /// be nice again
+    Forbidden,
+
+    V2 {
+        last: Option<u8>,
+
+        /// Only needed until a fencepost has been found
+        before: Option<u8>,
+
+        /// Pairs that can be repeated without overlapping, only needed
+        /// until a repeat has been found. Kept sorted
+        seen: Vec<(u8, u8)>,
+
+        /// The most recent pair, which can't be repeated straight away
+        pending: Option<(u8, u8)>,
+
+        repeated: bool,
+        fencepost: bool,
+    },
+}
+
+impl State {
+    fn start(version: Version) -> Self {
+        match version {
+            Version::V1 => State::V1 { vowels: 0, last: None, doubled: false },
+            Version::V2 => State::V2 {
+                last: None,
+                before: None,
+                seen: Vec::new(),
+                pending: None,
+                repeated: false,
+                fencepost: false,
+            },
+        }
+    }
+
+    fn step(&self, letter: u8) -> Self {
+        match self {
+            State::V1 { vowels, last, doubled } => {
+                let forbidden = matches!(
+                    (*last, letter),
+                    (Some(b'a'), b'b') | (Some(b'c'), b'd') | (Some(b'p'), b'q') | (Some(b'x'), b'y')
+                );
+
+                if forbidden {
+                    return State::Forbidden;
+                }
+
+                let vowel = matches!(letter, b'a' | b'e' | b'i' | b'o' | b'u');
+                State::V1 {
+                    vowels: (vowels + u8::from(vowel)).min(3),
+                    last: Some(letter),
+                    doubled: *doubled || *last == Some(letter),
+                }
+            }
+            State::Forbidden => State::Forbidden,
+            State::V2 { last, before, seen, pending, repeated, fencepost } => {
+                let current = last.map(|last| (last, letter));
+                let repeated = *repeated || current.is_some_and(|pair| seen.binary_search(&pair).is_ok());
+                let fencepost = *fencepost || *before == Some(letter);
+
+                // Forget anything that can't make a difference any more,
+                // so that equivalent states look the same
+                let mut seen = seen.clone();
+                if repeated {
+                    seen.clear();
+                } else if let Some(pair) = pending {
+                    if let Err(index) = seen.binary_search(pair) {
+                        seen.insert(index, *pair);
+                    }
+                }
+
+                State::V2 {
+                    last: Some(letter),
+                    before: if fencepost { None } else { *last },
+                    seen,
+                    pending: if repeated { None } else { current },
+                    repeated,
+                    fencepost,
+                }
+            }
+        }
+    }
+
+    fn is_nice(&self) -> bool {
+        match self {
+            State::V1 { vowels, doubled, .. } => *vowels >= 3 && *doubled,
+            State::Forbidden => false,
+            State::V2 { repeated, fencepost, .. } => *repeated && *fencepost,
+        }
+    }
+}
+
+/// An alphabet with something other than ASCII in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphabetError {
+    pub position: usize,
+    pub found: char,
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected an ASCII letter at position {}, found {:?}", self.position, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AlphabetError {}
+
+/// The number of (naughty, nice) ways to finish a string, each of which
+/// is `None` if there are too many to count
+type Counts = (Option<u128>, Option<u128>);
+
+/// The string has ended, so it's one way or the other
+fn finished(state: &State) -> Counts {
+    match state.is_nice() {
+        true => (Some(0), Some(1)),
+        false => (Some(1), Some(0)),
+    }
+}
+
+fn add(a: Counts, b: Counts) -> Counts {
+    let sum = |a: Option<u128>, b: Option<u128>| a?.checked_add(b?);
+    (sum(a.0, b.0), sum(a.1, b.1))
+}
+
+/// Counts the nice strings of a given length over an alphabet, and
+/// picks them at random
+#[derive(Debug, Clone)]
+pub struct NiceStrings {
+    version: Version,
+    alphabet: Vec<u8>,
+
+    /// The ways to finish from every state, for each number of letters
+    /// still to go from one up, kept from the last length that was
+    /// sampled
+    sampled: Option<(usize, Vec<BTreeMap<State, Counts>>)>,
+}
+
+impl NiceStrings {
+    /// Strings made up of the ASCII letters of `alphabet`, judged by one
+    /// of the versions of the rules
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// assert!(NiceStrings::new(Version::V1, "abc").is_ok());
+    /// assert_eq!(NiceStrings::new(Version::V1, "abé").unwrap_err(), AlphabetError { position: 2, found: 'é' });
+    /// ```
+    pub fn new(version: Version, alphabet: &str) -> Result<Self, AlphabetError> {
+        if let Some((position, found)) = alphabet.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(AlphabetError { position, found });
+        }
+
+        let mut alphabet = alphabet.bytes().collect::<Vec<_>>();
+        alphabet.sort();
+        alphabet.dedup();
+
+        Ok(Self { version, alphabet, sampled: None })
+    }
+
+    /// The states the machine can be in after each number of letters, up
+    /// to `done`. Once a level turns out the same as the one before it,
+    /// every later level will be too, so the levels stop there
+    fn levels(&self, done: usize) -> Vec<BTreeSet<State>> {
+        let mut levels = vec![BTreeSet::from([State::start(self.version)])];
+        while levels.len() <= done {
+            let last = levels.last().expect("There's always the start");
+            let next = last
+                .iter()
+                .flat_map(|state| self.alphabet.iter().map(|&letter| state.step(letter)))
+                .collect::<BTreeSet<_>>();
+
+            if next == *last {
+                break;
+            }
+
+            levels.push(next);
+        }
+
+        levels
+    }
+
+    /// Work back from the end of strings of `length` letters, handing
+    /// `each` the ways to finish from every state that can still have
+    /// some number of letters to go, from one up to `length`. Finished
+    /// strings are judged as they're reached rather than kept, since
+    /// there are far more of them than anything else. Returns the ways to
+    /// finish from the start
+    fn count_back(&self, length: usize, mut each: impl FnMut(&BTreeMap<State, Counts>)) -> Counts {
+        let levels = self.levels(length.saturating_sub(1));
+        let level = |done: usize| &levels[done.min(levels.len() - 1)];
+
+        let mut row = BTreeMap::new();
+        for remaining in 1..=length {
+            let next = level(length - remaining)
+                .iter()
+                .map(|state| {
+                    let counts = self.alphabet.iter().fold((Some(0), Some(0)), |counts, &letter| {
+                        let after = state.step(letter);
+                        add(counts, if remaining == 1 { finished(&after) } else { row[&after] })
+                    });
+                    (state.clone(), counts)
+                })
+                .collect();
+
+            row = next;
+            each(&row);
+        }
+
+        match length {
+            0 => finished(&State::start(self.version)),
+            _ => row[&State::start(self.version)],
+        }
+    }
+
+    /// The number of nice strings of a given length, if there aren't too
+    /// many of them to count
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// let letters = "abcdefghijklmnopqrstuvwxyz";
+    /// assert_eq!(NiceStrings::new(Version::V1, "ab").unwrap().count_nice(3), Some(1));
+    /// assert_eq!(NiceStrings::new(Version::V1, "ae").unwrap().count_nice(3), Some(6));
+    /// assert_eq!(NiceStrings::new(Version::V2, "a").unwrap().count_nice(4), Some(1));
+    /// assert_eq!(NiceStrings::new(Version::V1, letters).unwrap().count_nice(16), Some(10884775807103473506488));
+    /// assert!(NiceStrings::new(Version::V1, letters).unwrap().count_nice(27).is_some());
+    /// assert_eq!(NiceStrings::new(Version::V1, letters).unwrap().count_nice(28), None);
+    ///
+    /// // There are far too many naughty strings to count, but never any nice ones
+    /// assert_eq!(NiceStrings::new(Version::V1, "bc").unwrap().count_nice(200), Some(0));
+    /// ```
+    pub fn count_nice(&self, length: usize) -> Option<u128> {
+        self.count_back(length, |_| {}).1
+    }
+
+    /// The number of naughty strings of a given length, if there aren't
+    /// too many of them to count
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// assert_eq!(NiceStrings::new(Version::V1, "ae").unwrap().count_naughty(3), Some(2));
+    /// assert_eq!(NiceStrings::new(Version::V2, "xy").unwrap().count_naughty(5), Some(14));
+    /// ```
+    pub fn count_naughty(&self, length: usize) -> Option<u128> {
+        self.count_back(length, |_| {}).0
+    }
+
+    /// Pick a string uniformly at random from all of the nice (or all of
+    /// the naughty) strings of a given length, if there are any and there
+    /// aren't too many to count
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// # use aoc2015::rng::Rng;
+    /// let mut rng = Rng::new(2015);
+    /// let mut strings = NiceStrings::new(Version::V1, "abcdefghijklmnopqrstuvwxyz").unwrap();
+    ///
+    /// let nice = strings.sample(16, true, &mut rng).unwrap();
+    /// assert_eq!(nice.len(), 16);
+    /// assert!(is_nice_v1(&nice));
+    ///
+    /// let naughty = strings.sample(16, false, &mut rng).unwrap();
+    /// assert!(!is_nice_v1(&naughty));
+    ///
+    /// assert_eq!(NiceStrings::new(Version::V2, "ab").unwrap().sample(3, true, &mut rng), None);
+    /// assert_eq!(strings.sample(28, true, &mut rng), None);
+    /// ```
+    pub fn sample(&mut self, length: usize, nice: bool, rng: &mut Rng) -> Option<String> {
+        let pick = |(naughty, nice_count): Counts| if nice { nice_count } else { naughty };
+
+        if self.sampled.as_ref().map(|(sampled, _)| *sampled) != Some(length) {
+            let mut rows = Vec::with_capacity(length);
+            self.count_back(length, |row| rows.push(row.clone()));
+            self.sampled = Some((length, rows));
+        }
+
+        let (_, rows) = self.sampled.as_ref().expect("The rows were just filled in");
+
+        let mut state = State::start(self.version);
+        let mut chosen = Vec::with_capacity(length);
+        for remaining in (0..length).rev() {
+            // Each letter is weighted by how many ways there are to finish
+            // the string after it
+            let weights = self.alphabet
+                .iter()
+                .map(|&letter| {
+                    let after = state.step(letter);
+                    let counts = if remaining == 0 { finished(&after) } else { rows[remaining - 1][&after] };
+                    Some((letter, pick(counts)?))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let total = weights.iter().try_fold(0u128, |total, &(_, weight)| total.checked_add(weight))?;
+            if total == 0 {
+                return None;
+            }
+
+            let mut target = rng.below_u128(total);
+            let letter = weights
+                .into_iter()
+                .find(|&(_, weight)| {
+                    let found = target < weight;
+                    target = target.saturating_sub(weight);
+                    found
+                })
+                .map(|(letter, _)| letter)
+                .expect("The target is always below the total weight");
+
+            state = state.step(letter);
+            chosen.push(letter);
+        }
+
+        (state.is_nice() == nice).then(|| String::from_utf8(chosen).expect("The alphabet was checked to be ASCII"))
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::day5::{is_nice_v1, is_nice_v2};
+
+    /// Every string of a given length over an alphabet
+    fn every_string(alphabet: &str, length: usize) -> Vec<String> {
+        (0..length).fold(vec![String::new()], |strings, _| {
+            strings
+                .iter()
+                .flat_map(|s| alphabet.chars().map(move |c| format!("{s}{c}")))
+                .collect()
+        })
+    }
+
+    #[test]
+    pub fn counts_match_brute_force() {
+        for (alphabet, length) in [("abcdex", 6), ("aeiouxy", 5), ("abpqc", 7)] {
+            let strings = every_string(alphabet, length);
+            let nice_v1 = strings.iter().filter(|s| is_nice_v1(s)).count() as u128;
+            let nice_v2 = strings.iter().filter(|s| is_nice_v2(s)).count() as u128;
+
+            let v1 = NiceStrings::new(Version::V1, alphabet).unwrap();
+            let v2 = NiceStrings::new(Version::V2, alphabet).unwrap();
+            assert_eq!(v1.count_nice(length), Some(nice_v1), "{alphabet}");
+            assert_eq!(v1.count_naughty(length), Some(strings.len() as u128 - nice_v1), "{alphabet}");
+            assert_eq!(v2.count_nice(length), Some(nice_v2), "{alphabet}");
+            assert_eq!(v2.count_naughty(length), Some(strings.len() as u128 - nice_v2), "{alphabet}");
+        }
+    }
+
+    #[test]
+    pub fn samples_follow_the_rules() {
+        let mut rng = Rng::new(5);
+        let mut v1 = NiceStrings::new(Version::V1, "abcdefghijklmnopqrstuvwxyz").unwrap();
+        let mut v2 = NiceStrings::new(Version::V2, "abcdef").unwrap();
+
+        for _ in 0..100 {
+            assert!(is_nice_v1(&v1.sample(16, true, &mut rng).unwrap()));
+            assert!(!is_nice_v1(&v1.sample(16, false, &mut rng).unwrap()));
+            assert!(is_nice_v2(&v2.sample(8, true, &mut rng).unwrap()));
+            assert!(!is_nice_v2(&v2.sample(8, false, &mut rng).unwrap()));
+        }
+    }
+
+    #[test]
+    pub fn samples_are_uniform() {
+        // There are only six nice strings here, so each should come up
+        // about a sixth of the time
+        let mut rng = Rng::new(11);
+        let mut strings = NiceStrings::new(Version::V1, "ae").unwrap();
+        let mut seen = BTreeMap::new();
+        for _ in 0..6000 {
+            *seen.entry(strings.sample(3, true, &mut rng).unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(seen.len(), 6);
+        assert!(seen.values().all(|&count| (900..1100).contains(&count)), "{seen:?}");
+    }
+
+    #[test]
+    pub fn long_strings_dont_recurse() {
+        let strings = NiceStrings::new(Version::V1, "a").unwrap();
+        assert_eq!(strings.count_nice(1_000_000), Some(1));
+        assert_eq!(strings.count_naughty(1_000_000), Some(0));
+
+        // Nearly every string is nice, far too many to count, but the
+        // few naughty ones still can be
+        let strings = NiceStrings::new(Version::V2, "xy").unwrap();
+        assert_eq!(strings.count_nice(200), None);
+        assert_eq!(strings.count_naughty(200), Some(4));
+    }
+}
@@ -0,0 +1,469 @@
+//! Nice string policies built out of composable rules, which can also
+//! be loaded from a simple text format
+//!
+//! A rule file holds one policy per line, as a name and an expression
+//! combining the built in rules with `and`, `or`, `not`, `at_least`
+//! and parentheses. Blank lines and anything after a `#` are ignored.
+//!
+//! ```text
+//! # The policies from the puzzle
+//! v1 = vowels(3) and doubled and not contains(ab, cd, pq, xy)
+//! v2 = pair and fencepost
+//!
+//! # Any two of the v1 rules will do
+//! lenient = at_least(2, vowels(3), doubled, not contains(ab, cd, pq, xy))
+//! ```
+//!
+//! The built in rules are
+//!
+//! * `vowels(n)` - at least `n` vowels
+//! * `doubled` - a letter that appears twice in a row
+//! * `contains(s, ...)` - any of the given substrings
+//! * `pair` - a pair of letters that appears twice without overlapping
+//! * `fencepost` - a letter that repeats with exactly one letter between
+//!
+//! Rules can be nested up to [`MAX_DEPTH`] brackets and `not`s deep.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, alphanumeric1, char, digit1, multispace0};
+use nom::combinator::{all_consuming, map, map_res, recognize};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated};
+use nom::IResult;
+
+use super::{has_doubled_letter, has_fenceposts, has_nonoverlapping_pair};
+use crate::prelude::*;
+
+/// How many brackets and `not`s a rule can be nested inside. Anything
+/// deeper is an error, rather than a stack overflow
+pub const MAX_DEPTH: usize = 128;
+
+/// Something a string can be tested against
+pub trait Rule {
+    /// Whether the string follows the rule
+    fn matches(&self, s: &str) -> bool;
+
+    /// A rule that needs both this and another rule to match
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// let rule = Vowels(3).and(DoubledLetter);
+    /// assert!(rule.matches("aaa"));
+    /// assert!(!rule.matches("aei"));
+    /// ```
+    fn and<R: Rule>(self, other: R) -> And<Self, R>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// A rule that needs either this or another rule to match
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// let rule = Vowels(3).or(DoubledLetter);
+    /// assert!(rule.matches("aei"));
+    /// assert!(rule.matches("xx"));
+    /// assert!(!rule.matches("xyz"));
+    /// ```
+    fn or<R: Rule>(self, other: R) -> Or<Self, R>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// A rule that matches whenever this one doesn't
+    ///
+    /// ```
+    /// # use aoc2015::day5::*;
+    /// let rule = DoubledLetter.not();
+    /// assert!(rule.matches("xyz"));
+    /// assert!(!rule.matches("xx"));
+    /// ```
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+
+    /// Box the rule up, for mixing with rules of other types
+    fn boxed(self) -> Box<dyn Rule>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+impl<R: Rule + ?Sized> Rule for Box<R> {
+    fn matches(&self, s: &str) -> bool {
+        (**self).matches(s)
+    }
+}
+
+/// Both rules must match
+#[derive(Debug, Clone)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<A: Rule, B: Rule> Rule for And<A, B> {
+    fn matches(&self, s: &str) -> bool {
+        self.0.matches(s) && self.1.matches(s)
+    }
+}
+
+/// Either rule must match
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A: Rule, B: Rule> Rule for Or<A, B> {
+    fn matches(&self, s: &str) -> bool {
+        self.0.matches(s) || self.1.matches(s)
+    }
+}
+
+/// The rule must not match
+#[derive(Debug, Clone)]
+pub struct Not<A>(pub A);
+
+impl<A: Rule> Rule for Not<A> {
+    fn matches(&self, s: &str) -> bool {
+        !self.0.matches(s)
+    }
+}
+
+/// Some minimum number of a set of rules must match
+pub struct AtLeast {
+    count: usize,
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Rule for AtLeast {
+    fn matches(&self, s: &str) -> bool {
+        self.rules.iter().filter(|rule| rule.matches(s)).count() >= self.count
+    }
+}
+
+/// A rule that needs at least `count` of `rules` to match
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// let rule = at_least(2, vec![Vowels(3).boxed(), DoubledLetter.boxed(), Fenceposts.boxed()]);
+/// assert!(rule.matches("aaa"));
+/// assert!(rule.matches("xyxx"));
+/// assert!(!rule.matches("aeixyz"));
+/// ```
+pub fn at_least(count: usize, rules: Vec<Box<dyn Rule>>) -> AtLeast {
+    AtLeast { count, rules }
+}
+
+/// At least some number of vowels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vowels(pub usize);
+
+impl Rule for Vowels {
+    fn matches(&self, s: &str) -> bool {
+        s.chars().filter(|c| "aeiou".contains(*c)).count() >= self.0
+    }
+}
+
+/// A letter that appears twice in a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoubledLetter;
+
+impl Rule for DoubledLetter {
+    fn matches(&self, s: &str) -> bool {
+        has_doubled_letter(s)
+    }
+}
+
+/// Any one of a set of substrings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contains(pub Vec<String>);
+
+impl Rule for Contains {
+    fn matches(&self, s: &str) -> bool {
+        self.0.iter().any(|needle| s.contains(needle.as_str()))
+    }
+}
+
+/// A pair of letters that appears at least twice without overlapping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonOverlappingPair;
+
+impl Rule for NonOverlappingPair {
+    fn matches(&self, s: &str) -> bool {
+        has_nonoverlapping_pair(s)
+    }
+}
+
+/// A letter that repeats with exactly one letter in between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fenceposts;
+
+impl Rule for Fenceposts {
+    fn matches(&self, s: &str) -> bool {
+        has_fenceposts(s)
+    }
+}
+
+/// The rules for the first version of nice strings, as rules
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// assert!(rules_v1().matches("ugknbfddgicrmopn"));
+/// assert!(!rules_v1().matches("haegwjzuvuyypxyu"));
+/// ```
+pub fn rules_v1() -> impl Rule {
+    let forbidden = ["ab", "cd", "pq", "xy"].map(String::from).to_vec();
+    Vowels(3).and(DoubledLetter).and(Contains(forbidden).not())
+}
+
+/// The rules for the second version of nice strings, as rules
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// assert!(rules_v2().matches("qjhvhtzxzqqjkmpb"));
+/// assert!(!rules_v2().matches("uurcxstgmygtbstg"));
+/// ```
+pub fn rules_v2() -> impl Rule {
+    NonOverlappingPair.and(Fenceposts)
+}
+
+/// A named rule loaded from a rule file
+pub struct Policy {
+    pub name: String,
+    pub rule: Box<dyn Rule>,
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Policy").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+impl Rule for Policy {
+    fn matches(&self, s: &str) -> bool {
+        self.rule.matches(s)
+    }
+}
+
+/// A rule file that couldn't be understood
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    /// The line the problem is on, counting from one
+    pub line: usize,
+
+    /// The text that couldn't be parsed
+    pub text: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Couldn't understand the rule on line {}: {}", self.line, self.text)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RuleError {}
+
+/// Load every policy from the text of a rule file
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// let policies = parse_policies("
+///     ## The policies from the puzzle
+///     v1 = vowels(3) and doubled and not contains(ab, cd, pq, xy)
+///     v2 = pair and fencepost
+///
+///     lenient = at_least(2, vowels(3), doubled, not contains(ab, cd, pq, xy))
+/// ").unwrap();
+///
+/// assert_eq!(policies.len(), 3);
+/// assert_eq!(policies[1].name, "v2");
+/// assert!(policies[0].matches("ugknbfddgicrmopn"));
+/// assert!(policies[2].matches("haegwjzuvuyypxyu"));
+///
+/// let err = parse_policies("v1 = vowels(3)\nv2 = pair and and").unwrap_err();
+/// assert_eq!(err.line, 2);
+///
+/// let nested = |depth| format!("deep = {}doubled{}", "(".repeat(depth), ")".repeat(depth));
+/// assert!(parse_policies(&nested(MAX_DEPTH)).is_ok());
+/// assert!(parse_policies(&nested(MAX_DEPTH + 1)).is_err());
+/// ```
+pub fn parse_policies(text: &str) -> Result<Vec<Policy>, RuleError> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split('#').next().unwrap_or_default().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line, text)| {
+            all_consuming(policy)(text)
+                .map(|(_, policy)| policy)
+                .map_err(|_| RuleError { line, text: text.to_string() })
+        })
+        .collect()
+}
+
+/// Load a single rule from an expression
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// let rule = parse_rule("(doubled or fencepost) and not vowels(1)").unwrap();
+/// assert!(rule.matches("xyx"));
+/// assert!(!rule.matches("xax"));
+/// assert!(parse_rule("doubled or").is_err());
+/// assert!(parse_rule(&"not ".repeat(MAX_DEPTH + 1)).is_err());
+/// ```
+pub fn parse_rule(text: &str) -> Result<Box<dyn Rule>, RuleError> {
+    all_consuming(ws(|input| expression(input, 0)))(text)
+        .map(|(_, rule)| rule)
+        .map_err(|_| RuleError { line: 1, text: text.to_string() })
+}
+
+fn policy(input: &str) -> IResult<&str, Policy> {
+    map(
+        separated_pair(ws(identifier), char('='), ws(|input| expression(input, 0))),
+        |(name, rule)| Policy { name: name.to_string(), rule },
+    )(input)
+}
+
+fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(alpha1, many0(alt((alphanumeric1, tag("_"))))))(input)
+}
+
+fn keyword<'a>(word: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |input| {
+        let (next, found) = identifier(input)?;
+        if found == word {
+            Ok((next, found))
+        } else {
+            Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+        }
+    }
+}
+
+fn number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Go one bracket or `not` deeper than `depth`, which is a failure if
+/// that's too deep
+fn deeper(input: &str, depth: usize) -> Result<usize, nom::Err<Error<&str>>> {
+    match depth < MAX_DEPTH {
+        true => Ok(depth + 1),
+        false => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+    }
+}
+
+fn parenthesized<'a, O>(
+    inner: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    delimited(terminated(char('('), multispace0), inner, preceded(multispace0, char(')')))
+}
+
+/// A chain of rules separated by `or`, `depth` brackets and `not`s down.
+/// Chains are kept flat, so that a long one doesn't nest the rules any
+/// deeper
+fn expression(input: &str, depth: usize) -> IResult<&str, Box<dyn Rule>> {
+    map(separated_list1(ws(keyword("or")), |input| conjunction(input, depth)), |mut rules| match rules.len() {
+        1 => rules.pop().expect("There's one rule"),
+        _ => at_least(1, rules).boxed(),
+    })(input)
+}
+
+fn conjunction(input: &str, depth: usize) -> IResult<&str, Box<dyn Rule>> {
+    map(separated_list1(ws(keyword("and")), |input| unary(input, depth)), |mut rules| match rules.len() {
+        1 => rules.pop().expect("There's one rule"),
+        count => at_least(count, rules).boxed(),
+    })(input)
+}
+
+fn unary(input: &str, depth: usize) -> IResult<&str, Box<dyn Rule>> {
+    alt((
+        map(preceded(pair(keyword("not"), multispace0), |input| unary(input, deeper(input, depth)?)), |rule| {
+            Box::new(Not(rule)) as Box<dyn Rule>
+        }),
+        parenthesized(|input| expression(input, deeper(input, depth)?)),
+        |input| atom(input, depth),
+    ))(input)
+}
+
+fn atom(input: &str, depth: usize) -> IResult<&str, Box<dyn Rule>> {
+    let (next, name) = identifier(input)?;
+    match name {
+        "vowels" => map(parenthesized(number), |count| Vowels(count).boxed())(next),
+        "contains" => map(parenthesized(separated_list1(ws(char(',')), alpha1)), |needles| {
+            Contains(needles.into_iter().map(String::from).collect()).boxed()
+        })(next),
+        "at_least" => map(
+            parenthesized(separated_pair(
+                number,
+                ws(char(',')),
+                separated_list1(ws(char(',')), |input| expression(input, deeper(input, depth)?)),
+            )),
+            |(count, rules)| at_least(count, rules).boxed(),
+        )(next),
+        "doubled" => Ok((next, DoubledLetter.boxed())),
+        "pair" => Ok((next, NonOverlappingPair.boxed())),
+        "fencepost" => Ok((next, Fenceposts.boxed())),
+        _ => Err(nom::Err::Error(Error::new(input, ErrorKind::Tag))),
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+
+    static POLICIES: &str = "
+        v1 = vowels(3) and doubled and not contains(ab, cd, pq, xy)
+        v2 = pair and fencepost
+    ";
+
+    #[test]
+    pub fn problem1() {
+        let policies = parse_policies(POLICIES).unwrap();
+        let nice_strings = bundled(5)
+            .unwrap()
+            .lines()
+            .filter(|line| policies[0].matches(line))
+            .count();
+
+        assert_eq!(nice_strings, 255);
+        assert_eq!(bundled(5).unwrap().lines().filter(|line| rules_v1().matches(line)).count(), 255);
+    }
+
+    #[test]
+    pub fn problem2() {
+        let policies = parse_policies(POLICIES).unwrap();
+        let nice_strings = bundled(5)
+            .unwrap()
+            .lines()
+            .filter(|line| policies[1].matches(line))
+            .count();
+
+        assert_eq!(nice_strings, 55);
+        assert_eq!(bundled(5).unwrap().lines().filter(|line| rules_v2().matches(line)).count(), 55);
+    }
+
+    #[test]
+    pub fn deep_rules_are_errors() {
+        let brackets = "(".repeat(50_000) + "doubled" + &")".repeat(50_000);
+        assert!(parse_rule(&brackets).is_err());
+        assert!(parse_rule(&"not ".repeat(50_000)).is_err());
+        assert!(parse_rule(&format!("at_least(1, {})", "not ".repeat(50_000) + "pair")).is_err());
+
+        // Long chains don't nest, so they're fine however long they get
+        let chain = vec!["doubled"; 50_000].join(" and ");
+        assert!(parse_rule(&chain).unwrap().matches("xx"));
+    }
+}
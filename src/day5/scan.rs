@@ -0,0 +1,229 @@
+//! A single pass state machine that checks every rule at once, for
+//! classifying lots of strings without allocating
+
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+
+use super::is_padding;
+
+/// Every pair of bytes, one bit each
+const PAIRS: usize = 1 << 16;
+
+/// Checks the v1 and v2 rules together, a byte at a time
+#[derive(Clone)]
+pub struct Scanner {
+    vowels: u8,
+    doubled: bool,
+    forbidden: bool,
+    fencepost: bool,
+
+    /// Whether a pair has turned up again before the latest byte that
+    /// isn't padding. Padding at the end doesn't count, but there's no
+    /// knowing it's at the end until the string is over
+    repeated_pair: bool,
+
+    /// The same, but including whatever has come since, in case that
+    /// turns out not to be padding
+    repeated_pair_so_far: bool,
+
+    /// How many bytes have been seen since the padding at the start, up
+    /// to three, so that pairs reaching into it are left out
+    unpadded: u8,
+
+    /// The last three bytes seen, most recent first
+    previous: [Option<u8>; 3],
+
+    /// Pairs that ended at least two bytes ago, so that matching one
+    /// of them means the pairs can't overlap
+    seen: [u64; PAIRS / 64],
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self {
+            vowels: 0,
+            doubled: false,
+            forbidden: false,
+            fencepost: false,
+            repeated_pair: false,
+            repeated_pair_so_far: false,
+            unpadded: 0,
+            previous: [None; 3],
+            seen: [0; PAIRS / 64],
+        }
+    }
+}
+
+impl Scanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget everything, ready to start on a new string
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Feed the next byte of the string through the rules
+    pub fn push(&mut self, byte: u8) {
+        if matches!(byte, b'a' | b'e' | b'i' | b'o' | b'u') {
+            self.vowels = self.vowels.saturating_add(1);
+        }
+
+        if let [Some(last), before, _] = self.previous {
+            self.doubled |= last == byte;
+            self.forbidden |= matches!([last, byte], [b'a', b'b'] | [b'c', b'd'] | [b'p', b'q'] | [b'x', b'y']);
+            self.fencepost |= before == Some(byte);
+        }
+
+        if self.unpadded > 0 || !is_padding(byte) {
+            self.push_pair(byte);
+        }
+
+        self.previous = [Some(byte), self.previous[0], self.previous[1]];
+    }
+
+    /// Look for repeated pairs, leaving out the padding at the start
+    fn push_pair(&mut self, byte: u8) {
+        // A pair only becomes fair game once it can no longer overlap
+        // with the current one, which is two bytes later
+        if let (3, [_, Some(before), Some(earliest)]) = (self.unpadded, self.previous) {
+            let older = usize::from(u16::from_be_bytes([earliest, before]));
+            self.seen[older / 64] |= 1 << (older % 64);
+        }
+
+        if let Some(last) = self.previous[0].filter(|_| self.unpadded > 0) {
+            let current = usize::from(u16::from_be_bytes([last, byte]));
+            self.repeated_pair_so_far |= self.seen[current / 64] & (1 << (current % 64)) != 0;
+        }
+
+        self.unpadded = (self.unpadded + 1).min(3);
+        if !is_padding(byte) {
+            self.repeated_pair = self.repeated_pair_so_far;
+        }
+    }
+
+    /// Whether everything so far is nice under the v1 rules
+    pub fn is_nice_v1(&self) -> bool {
+        self.vowels >= 3 && self.doubled && !self.forbidden
+    }
+
+    /// Whether everything so far is nice under the v2 rules
+    pub fn is_nice_v2(&self) -> bool {
+        self.repeated_pair && self.fencepost
+    }
+}
+
+/// Whether a string is nice under the (v1, v2) rules, from a single
+/// pass over it
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// assert_eq!(scan("ugknbfddgicrmopn"), (true, false));
+/// assert_eq!(scan("qjhvhtzxzqqjkmpb"), (false, true));
+/// assert_eq!(scan("aaa"), (true, false));
+/// assert_eq!(scan("aaaa"), (true, true));
+/// assert_eq!(scan("  x  "), scan("x"));
+/// ```
+pub fn scan(s: &str) -> (bool, bool) {
+    let mut scanner = Scanner::new();
+    s.bytes().for_each(|byte| scanner.push(byte));
+    (scanner.is_nice_v1(), scanner.is_nice_v2())
+}
+
+/// Tallies of naughty and nice strings
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NiceCounts {
+    pub lines: usize,
+    pub v1: usize,
+    pub v2: usize,
+}
+
+#[cfg(feature = "std")]
+impl NiceCounts {
+    fn tally(&mut self, scanner: &Scanner) {
+        self.lines += 1;
+        self.v1 += usize::from(scanner.is_nice_v1());
+        self.v2 += usize::from(scanner.is_nice_v2());
+    }
+}
+
+/// Count the nice strings under both versions of the rules, reading one
+/// string per line. Line endings may be `\n` or `\r\n`.
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// let input = "ugknbfddgicrmopn\r\nqjhvhtzxzqqjkmpb\r\nxxyxx\n";
+/// let counts = count_nice(input.as_bytes()).unwrap();
+/// assert_eq!(counts, NiceCounts { lines: 3, v1: 1, v2: 2 });
+/// ```
+#[cfg(feature = "std")]
+pub fn count_nice(mut reader: impl BufRead) -> io::Result<NiceCounts> {
+    let mut counts = NiceCounts::default();
+    let mut scanner = Scanner::new();
+    let mut in_line = false;
+    let mut carriage_return = false;
+
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+
+        for &byte in buffer {
+            if byte == b'\n' {
+                counts.tally(&scanner);
+                scanner.reset();
+                in_line = false;
+                carriage_return = false;
+                continue;
+            }
+
+            // Hold on to a carriage return until we know it isn't part
+            // of a line ending
+            if carriage_return {
+                scanner.push(b'\r');
+            }
+
+            carriage_return = byte == b'\r';
+            if !carriage_return {
+                scanner.push(byte);
+            }
+
+            in_line = true;
+        }
+
+        let used = buffer.len();
+        reader.consume(used);
+    }
+
+    if in_line {
+        if carriage_return {
+            scanner.push(b'\r');
+        }
+
+        counts.tally(&scanner);
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::day5::{is_nice_v1, is_nice_v2};
+    use crate::input::bundled;
+
+    #[test]
+    pub fn problems() {
+        let counts = count_nice(bundled(5).unwrap().as_bytes()).unwrap();
+        assert_eq!(counts, NiceCounts { lines: 1000, v1: 255, v2: 55 });
+    }
+
+    #[test]
+    pub fn agrees_with_predicates() {
+        for line in bundled(5).unwrap().lines() {
+            assert_eq!(scan(line), (is_nice_v1(line), is_nice_v2(line)), "{line}");
+        }
+    }
+}
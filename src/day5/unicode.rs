@@ -0,0 +1,289 @@
+//! Nice string rules for text that isn't plain ASCII
+//!
+//! The puzzle predicates work on bytes, which is quick but gives the
+//! wrong answers as soon as a letter takes more than one byte. Here the
+//! rules can instead look at whole characters or grapheme clusters, with
+//! a vowel set suited to the language and optionally ignoring case.
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{has_doubled_letter, has_forbidden_substring, has_fenceposts, has_nonoverlapping_pair, is_padding};
+
+pub const ENGLISH_VOWELS: &[&str] = &["a", "e", "i", "o", "u"];
+pub const FRENCH_VOWELS: &[&str] = &[
+    "a", "e", "i", "o", "u", "y", "à", "â", "æ", "é", "è", "ê", "ë", "î", "ï", "ô", "œ", "ù", "û", "ü", "ÿ",
+];
+pub const GERMAN_VOWELS: &[&str] = &["a", "e", "i", "o", "u", "ä", "ö", "ü"];
+pub const SPANISH_VOWELS: &[&str] = &["a", "e", "i", "o", "u", "á", "é", "í", "ó", "ú", "ü"];
+
+/// The substrings that make a string naughty under the v1 rules
+const FORBIDDEN: [&str; 4] = ["ab", "cd", "pq", "xy"];
+
+/// What counts as a single letter
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    /// Every byte is a letter. Fast, but only right for ASCII
+    #[default]
+    Ascii,
+
+    /// Every `char` is a letter
+    Chars,
+
+    /// Every extended grapheme cluster is a letter, so accents written
+    /// as combining marks stay attached to their letter
+    Graphemes,
+}
+
+/// How to apply the nice string rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub units: Units,
+
+    /// The letters that count as vowels. A grapheme also counts if the
+    /// character it starts with is one of them
+    pub vowels: Vec<String>,
+
+    /// Whether upper and lower case versions of a letter are the same
+    pub case_insensitive: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+impl Options {
+    /// The puzzle's own rules, on bytes with English vowels
+    pub fn ascii() -> Self {
+        Self { units: Units::Ascii, vowels: to_owned(ENGLISH_VOWELS), case_insensitive: false }
+    }
+
+    /// Rules on characters, with the given vowels
+    pub fn chars(vowels: &[&str]) -> Self {
+        Self { units: Units::Chars, vowels: to_owned(vowels), case_insensitive: false }
+    }
+
+    /// Rules on grapheme clusters, with the given vowels
+    pub fn graphemes(vowels: &[&str]) -> Self {
+        Self { units: Units::Graphemes, vowels: to_owned(vowels), case_insensitive: false }
+    }
+
+    /// The same rules, but ignoring case
+    pub fn case_insensitive(self) -> Self {
+        Self { case_insensitive: true, ..self }
+    }
+
+    /// Split a string up into its letters. They're kept as bytes, since
+    /// a letter can be a single byte out of a longer character when
+    /// working on bytes
+    fn letters<'a>(&self, s: &'a str) -> Vec<Cow<'a, [u8]>> {
+        let letters: Vec<&str> = match self.units {
+            Units::Ascii => {
+                return s
+                    .as_bytes()
+                    .chunks(1)
+                    .map(|letter| match self.case_insensitive {
+                        true => Cow::Owned(letter.to_ascii_lowercase()),
+                        false => Cow::Borrowed(letter),
+                    })
+                    .collect();
+            }
+            Units::Chars => s.char_indices().map(|(offset, c)| &s[offset..offset + c.len_utf8()]).collect(),
+            Units::Graphemes => s.graphemes(true).collect(),
+        };
+
+        letters
+            .into_iter()
+            .map(|letter| match self.case_insensitive {
+                true => Cow::Owned(letter.to_lowercase().into_bytes()),
+                false => Cow::Borrowed(letter.as_bytes()),
+            })
+            .collect()
+    }
+
+    /// The vowels, folded to the same case as the letters will be
+    fn vowel_set(&self) -> Vec<Cow<'_, str>> {
+        self.vowels
+            .iter()
+            .map(|vowel| match self.case_insensitive {
+                true => Cow::Owned(vowel.to_lowercase()),
+                false => Cow::Borrowed(vowel.as_str()),
+            })
+            .collect()
+    }
+
+    /// Which ASCII bytes are vowels, worked out up front so that the
+    /// bytes can be checked without comparing strings
+    fn ascii_vowels(&self) -> [bool; 128] {
+        let mut table = [false; 128];
+        for vowel in self.vowel_set() {
+            if let &[b] = vowel.as_bytes() {
+                if let Some(entry) = table.get_mut(usize::from(b)) {
+                    *entry = true;
+                }
+            }
+        }
+
+        table
+    }
+
+    /// Fold the case of an ASCII string, if we're asked to
+    fn ascii_case<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self.case_insensitive {
+            true => Cow::Owned(s.to_ascii_lowercase()),
+            false => Cow::Borrowed(s),
+        }
+    }
+}
+
+fn to_owned(vowels: &[&str]) -> Vec<String> {
+    vowels.iter().map(|&vowel| vowel.to_string()).collect()
+}
+
+/// Whether a letter is one of the vowels, or starts with one
+fn is_vowel(letter: &[u8], vowels: &[Cow<str>]) -> bool {
+    let first = core::str::from_utf8(letter)
+        .ok()
+        .and_then(|letter| letter.chars().next())
+        .map_or(letter.len(), char::len_utf8);
+
+    vowels.iter().any(|vowel| vowel.as_bytes() == letter || vowel.as_bytes() == &letter[..first])
+}
+
+/// Like [`has_nonoverlapping_pair`], this leaves out the padding at
+/// either end, which is any letter made only of padding bytes
+fn has_nonoverlapping_pair_in(letters: &[Cow<[u8]>]) -> bool {
+    let is_padding = |letter: &&Cow<[u8]>| letter.iter().all(|&byte| is_padding(byte));
+    let leading = letters.iter().take_while(is_padding).count();
+    let trailing = letters[leading..].iter().rev().take_while(is_padding).count();
+
+    let mut seen_pairs = BTreeMap::new();
+    letters[leading..letters.len() - trailing]
+        .windows(2)
+        .enumerate()
+        .any(|(current, pair)| current - *seen_pairs.entry(pair).or_insert(current) > 1)
+}
+
+/// See if the string matches all of the v1 rules, with the given idea of
+/// what letters and vowels are
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// // Bytes see the two halves of the `é` as different letters
+/// assert!(!is_nice_v1_with("ééàu", &Options::ascii()));
+/// assert!(is_nice_v1_with("ééàu", &Options::chars(FRENCH_VOWELS)));
+///
+/// // A combining accent stays with its letter as a grapheme
+/// assert!(!is_nice_v1_with("e\u{301}e\u{301}o", &Options::chars(FRENCH_VOWELS)));
+/// assert!(is_nice_v1_with("e\u{301}e\u{301}o", &Options::graphemes(FRENCH_VOWELS)));
+///
+/// assert!(!is_nice_v1_with("AaE", &Options::ascii()));
+/// assert!(is_nice_v1_with("AaE", &Options::ascii().case_insensitive()));
+/// assert!(!is_nice_v1_with("ÄäÖ", &Options::chars(GERMAN_VOWELS)));
+/// assert!(is_nice_v1_with("ÄäÖ", &Options::chars(GERMAN_VOWELS).case_insensitive()));
+/// ```
+pub fn is_nice_v1_with(s: &str, options: &Options) -> bool {
+    if options.units == Units::Ascii {
+        let s = options.ascii_case(s);
+        let table = options.ascii_vowels();
+        let vowels = s.bytes().filter(|&b| table.get(usize::from(b)) == Some(&true)).count();
+
+        return vowels >= 3 && has_doubled_letter(&s) && !has_forbidden_substring(&s);
+    }
+
+    let letters = options.letters(s);
+    let vowels = options.vowel_set();
+    letters.iter().filter(|letter| is_vowel(letter, &vowels)).count() >= 3
+        && letters.windows(2).any(|w| w[0] == w[1])
+        && !letters.windows(2).any(|w| FORBIDDEN.iter().any(|pair| pair.as_bytes().chunks(1).eq(w.iter().map(Cow::as_ref))))
+}
+
+/// See if the string matches all of the v2 rules, with the given idea of
+/// what letters are
+///
+/// ```
+/// # use aoc2015::day5::*;
+/// // Bytes find pairs and fenceposts inside the multibyte letters
+/// assert!(is_nice_v2_with("éé", &Options::ascii()));
+/// assert!(!is_nice_v2_with("éé", &Options::chars(FRENCH_VOWELS)));
+/// assert!(is_nice_v2_with("éaéaé", &Options::chars(FRENCH_VOWELS)));
+///
+/// assert!(!is_nice_v2_with("xyXyx", &Options::chars(ENGLISH_VOWELS)));
+/// assert!(is_nice_v2_with("xyXyx", &Options::chars(ENGLISH_VOWELS).case_insensitive()));
+/// ```
+pub fn is_nice_v2_with(s: &str, options: &Options) -> bool {
+    if options.units == Units::Ascii {
+        let s = options.ascii_case(s);
+        return has_nonoverlapping_pair(&s) && has_fenceposts(&s);
+    }
+
+    let letters = options.letters(s);
+    has_nonoverlapping_pair_in(&letters) && letters.windows(3).any(|w| w[0] == w[2])
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::day5::{is_nice_v1, is_nice_v2};
+    use crate::input::bundled;
+
+    #[test]
+    pub fn problem1() {
+        for options in [Options::ascii(), Options::chars(ENGLISH_VOWELS), Options::graphemes(ENGLISH_VOWELS)] {
+            let nice_strings = bundled(5)
+                .unwrap()
+                .lines()
+                .filter(|line| is_nice_v1_with(line, &options))
+                .count();
+
+            assert_eq!(nice_strings, 255, "{options:?}");
+        }
+    }
+
+    #[test]
+    pub fn problem2() {
+        for options in [Options::ascii(), Options::chars(ENGLISH_VOWELS), Options::graphemes(ENGLISH_VOWELS)] {
+            let nice_strings = bundled(5)
+                .unwrap()
+                .lines()
+                .filter(|line| is_nice_v2_with(line, &options))
+                .count();
+
+            assert_eq!(nice_strings, 55, "{options:?}");
+        }
+    }
+
+    #[test]
+    pub fn ascii_is_the_default() {
+        for line in bundled(5).unwrap().lines() {
+            assert_eq!(is_nice_v1_with(line, &Options::default()), is_nice_v1(line));
+            assert_eq!(is_nice_v2_with(line, &Options::default()), is_nice_v2(line));
+        }
+    }
+
+    #[test]
+    pub fn padding_is_the_same_for_every_unit() {
+        for options in [Options::ascii(), Options::chars(ENGLISH_VOWELS), Options::graphemes(ENGLISH_VOWELS)] {
+            assert!(!is_nice_v2_with("  x  ", &options), "{options:?}");
+            assert!(!is_nice_v2_with("\r\n\r\nx\r\n\r\n", &options), "{options:?}");
+            assert!(is_nice_v2_with(" xyxy ", &options), "{options:?}");
+        }
+    }
+
+    #[test]
+    pub fn ascii_letters_are_bytes() {
+        let letters = Options::ascii().case_insensitive().letters("Aé");
+        assert_eq!(letters, [&b"a"[..], &[0xc3], &[0xa9]]);
+
+        let options = Options::ascii();
+        let vowels = options.vowel_set();
+        assert!(is_vowel(b"e", &vowels));
+        assert!(!is_vowel(&[0xc3], &vowels));
+    }
+}
@@ -1,28 +1,21 @@
 //! Day 6 - Probably a Fire Hazard
 
+use std::fmt;
+
+use crate::grid::{Bounds, DenseGrid, Pos};
 use crate::prelude::*;
 use nom::{IResult, error::context, branch::alt, bytes::complete::tag, sequence::separated_pair};
 use nom::character::complete::{char, digit1};
+use nom::combinator::{all_consuming, map_res, value};
 use nom::sequence::tuple;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operation {
     TurnOn,
     TurnOff,
     Toggle,
 }
 
-impl From<&str> for Operation {
-    fn from(value: &str) -> Self {
-        match value.to_lowercase().as_str() {
-            "turn on" => Self::TurnOn,
-            "turn off" => Self::TurnOff,
-            "toggle" => Self::Toggle,
-            err => unimplemented!("Cannot handle operation: {err}"),
-        }
-    }
-}
-
 impl Operation {
     /// Parse an operation from tagged text
     ///
@@ -35,49 +28,59 @@ impl Operation {
     pub fn parse(input: &str) -> IResult<&str, Self> {
         context(
             "operation",
-            alt((tag("turn on"), tag("turn off"), tag("toggle")))
+            alt((
+                value(Self::TurnOn, tag("turn on")),
+                value(Self::TurnOff, tag("turn off")),
+                value(Self::Toggle, tag("toggle")),
+            ))
         )(input)
-        .map(|(next, res)| (next, res.into()))
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-pub struct Point {
-    pub x: usize,
-    pub y: usize,
+/// Parse a point from tagged text
+///
+/// ```
+/// # use aoc2015::day6::*;
+/// # use aoc2015::grid::Pos;
+/// assert_eq!(parse_point("123,456"), Ok(("", Pos::new(123, 456))));
+/// assert!(parse_point("123,abd").is_err());
+/// assert!(parse_point("123,99999999999999999999999").is_err());
+/// ```
+pub fn parse_point(input: &str) -> IResult<&str, Pos> {
+    let coordinate = || map_res(digit1, str::parse::<isize>);
+    context(
+        "point",
+        separated_pair(coordinate(), char(','), coordinate())
+    )(input)
+    .map(|(next, (x, y))| (next, Pos::new(x, y)))
 }
 
-impl Point {
-    /// Parse a point from tagged text
-    ///
-    /// ```
-    /// # use aoc2015::day6::*;
-    /// assert_eq!(Point::parse("123,456"), Ok(("", Point { x: 123, y: 456 })));
-    /// assert!(Point::parse("123,abd").is_err());
-    /// ```
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        context(
-            "point",
-            separated_pair(digit1, char(','), digit1)
-        )(input)
-        .map(|(next, res)| (next, res.into()))
-    }
+/// An instruction that couldn't be understood
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionError {
+    /// The text isn't an instruction at all
+    Syntax(String),
+
+    /// One of the corners is off the edge of the grid of lights
+    OutOfRange(Pos),
 }
 
-impl From<(&str, &str)> for Point {
-    fn from(value: (&str, &str)) -> Self {
-        Self {
-            x: value.0.parse().expect("Should have been a number"),
-            y: value.1.parse().expect("Should have been a number"),
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstructionError::Syntax(text) => write!(f, "Couldn't understand the instruction: {text}"),
+            InstructionError::OutOfRange(pos) => write!(f, "{pos} is outside the grid of lights"),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+impl std::error::Error for InstructionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Instruction {
     pub operation: Operation,
-    pub start_point: Point,
-    pub end_point: Point,
+    pub start_point: Pos,
+    pub end_point: Pos,
 }
 
 impl Instruction {
@@ -85,87 +88,196 @@ impl Instruction {
     ///
     /// ```
     /// # use aoc2015::day6::*;
+    /// # use aoc2015::grid::Pos;
     /// let i = Instruction::parse("turn on 1,1 through 2,2\n");
-    /// assert_eq!(i, Instruction {
+    /// assert_eq!(i, Ok(Instruction {
     ///     operation: Operation::TurnOn,
-    ///     start_point: Point { x: 1, y: 1 },
-    ///     end_point: Point { x: 2, y: 2 }
-    /// });
+    ///     start_point: Pos::new(1, 1),
+    ///     end_point: Pos::new(2, 2)
+    /// }));
+    ///
+    /// assert!(matches!(Instruction::parse("turn on 1,1 through 2,2 please"), Err(InstructionError::Syntax(_))));
+    /// assert_eq!(Instruction::parse("toggle 1,1 through 1000,2"), Err(InstructionError::OutOfRange(Pos::new(1000, 2))));
     /// ```
-    pub fn parse(input: &str) -> Self {
-        let (next, res) = context(
+    pub fn parse(input: &str) -> Result<Self, InstructionError> {
+        let (_, (operation, start_point, _, end_point)) = all_consuming(context(
             "instruction",
             tuple((
                 ws(Operation::parse),
-                ws(Point::parse),
+                ws(parse_point),
                 ws(tag("through")),
-                ws(Point::parse),
+                ws(parse_point),
             ))
-        )(input).expect("Instruction should have parsed correctly");
+        ))(input)
+        .map_err(|_| InstructionError::Syntax(input.to_string()))?;
 
-        if !next.is_empty() {
-            unimplemented!("Extra tokens in the input isn't handled");
+        let lights = Bounds::new(Pos::ORIGIN, Pos::new(GRID_SIZE as isize - 1, GRID_SIZE as isize - 1));
+        if let Some(&outside) = [start_point, end_point].iter().find(|&&corner| !lights.contains(corner)) {
+            return Err(InstructionError::OutOfRange(outside));
         }
 
-        let (operation, start_point, _, end_point) = res;
-        Instruction { operation, start_point, end_point }
+        Ok(Instruction { operation, start_point, end_point })
+    }
+
+    /// The rectangle of lights covered by the instruction
+    pub fn region(&self) -> Bounds {
+        Bounds::new(self.start_point, self.end_point)
+    }
+}
+
+/// The size of the (square) grid of lights
+pub const GRID_SIZE: usize = 1000;
+
+/// Follow the instructions, treating the lights as simple on/off
+/// switches, and say which are lit at the end
+///
+/// ```
+/// # use aoc2015::day6::*;
+/// # use aoc2015::grid::Pos;
+/// let lights = light_switches(&[Instruction::parse("turn on 1,2 through 3,4").unwrap()]);
+/// assert!(lights[Pos::new(3, 2)]);
+/// assert!(!lights[Pos::new(0, 2)]);
+/// ```
+pub fn light_switches(instructions: &[Instruction]) -> DenseGrid<bool> {
+    let mut grid = DenseGrid::new(GRID_SIZE, GRID_SIZE, false);
+    for i in instructions {
+        match i.operation {
+            Operation::TurnOn => grid.update(i.region(), |lit| *lit = true),
+            Operation::TurnOff => grid.update(i.region(), |lit| *lit = false),
+            Operation::Toggle => grid.update(i.region(), |lit| *lit = !*lit),
+        }
+    }
+
+    grid
+}
+
+/// Follow the instructions, treating the lights as simple on/off
+/// switches, and count how many are lit at the end
+///
+/// ```
+/// # use aoc2015::day6::*;
+/// let instructions = [
+///     Instruction::parse("turn on 0,0 through 999,999").unwrap(),
+///     Instruction::parse("toggle 0,0 through 999,0").unwrap(),
+///     Instruction::parse("turn off 499,499 through 500,500").unwrap(),
+/// ];
+/// assert_eq!(lights_lit(&instructions), 1_000_000 - 1000 - 4);
+/// ```
+pub fn lights_lit(instructions: &[Instruction]) -> usize {
+    light_switches(instructions).values().filter(|&&lit| lit).count()
+}
+
+/// Change the brightness of one light. A light can't get any brighter
+/// than `u32::MAX`, which would take billions of instructions
+fn adjust(brightness: &mut u32, operation: Operation) {
+    *brightness = match operation {
+        Operation::TurnOn => brightness.saturating_add(1),
+        Operation::TurnOff => brightness.saturating_sub(1),
+        Operation::Toggle => brightness.saturating_add(2),
+    };
+}
+
+/// Follow the instructions, treating the lights as having individual
+/// brightness controls, and find the total brightness at the end. The
+/// total can be more than a `u32` even when every light fits in one
+///
+/// ```
+/// # use aoc2015::day6::*;
+/// let instructions = [
+///     Instruction::parse("turn on 0,0 through 0,0").unwrap(),
+///     Instruction::parse("toggle 0,0 through 999,999").unwrap(),
+/// ];
+/// assert_eq!(total_brightness(&instructions), 2000001);
+/// ```
+pub fn total_brightness(instructions: &[Instruction]) -> u64 {
+    let mut grid = DenseGrid::new(GRID_SIZE, GRID_SIZE, 0u32);
+    for i in instructions {
+        grid.update(i.region(), |b| adjust(b, i.operation));
     }
 
+    grid.values().map(|&b| u64::from(b)).sum()
 }
 
 #[cfg(test)]
 mod answers {
     use super::*;
-    use std::collections::HashSet;
-    use std::collections::HashMap;
+    use crate::input::bundled;
 
-    static INPUT: &str = include_str!("./input/day6.txt");
+    fn instructions() -> Vec<Instruction> {
+        bundled(6).unwrap().lines().map(|line| Instruction::parse(line).unwrap()).collect()
+    }
 
     #[test]
     pub fn problem1() {
-        let grid = INPUT.lines()
-            .map(Instruction::parse)
-            .fold(HashSet::new(), |mut grid, i| {
-                for x in i.start_point.x..=i.end_point.x {
-                    for y in i.start_point.y..=i.end_point.y {
-                        let pos = Point { x, y };
-                        match i.operation {
-                            Operation::TurnOn => { grid.insert(pos); },
-                            Operation::TurnOff => { grid.remove(&pos); },
-                            Operation::Toggle => {
-                                if !grid.remove(&pos) {
-                                    grid.insert(pos);
-                                }
-                            },
-                        }
-                    }
-                }
-
-                grid
-            });
-
-        assert_eq!(grid.len(), 400410);
+        assert_eq!(lights_lit(&instructions()), 400410);
     }
 
     #[test]
     pub fn problem2() {
-        let grid: HashMap<Point, u32> = INPUT.lines()
-            .map(Instruction::parse)
-            .fold(HashMap::new(), |mut grid, i| {
-                for x in i.start_point.x..=i.end_point.x {
-                    for y in i.start_point.y..=i.end_point.y {
-                        let pos = Point { x, y };
-                        let entry = grid.entry(pos).or_insert(0);
-                        match i.operation {
-                            Operation::TurnOn => *entry += 1,
-                            Operation::TurnOff => *entry = entry.saturating_sub(1),
-                            Operation::Toggle => *entry += 2, }
-                    }
-                }
-
-                grid
-            });
-
-        assert_eq!(grid.values().sum::<u32>(), 15343601);
+        assert_eq!(total_brightness(&instructions()), 15343601);
+    }
+
+    #[test]
+    pub fn brightness_tops_out() {
+        let mut brightness = u32::MAX - 1;
+        adjust(&mut brightness, Operation::Toggle);
+        assert_eq!(brightness, u32::MAX);
+        adjust(&mut brightness, Operation::TurnOn);
+        assert_eq!(brightness, u32::MAX);
+        adjust(&mut brightness, Operation::TurnOff);
+        assert_eq!(brightness, u32::MAX - 1);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let corner = || (0..GRID_SIZE as isize, 0..GRID_SIZE as isize);
+        let operation = prop_oneof![Just(Operation::TurnOn), Just(Operation::TurnOff), Just(Operation::Toggle)];
+        (operation, corner(), corner()).prop_map(
+            |(operation, (x1, y1), (x2, y2))| Instruction {
+                operation,
+                start_point: Pos::new(x1.min(x2), y1.min(y2)),
+                end_point: Pos::new(x1.max(x2), y1.max(y2)),
+            },
+        )
+    }
+
+    fn toggle(region: &Instruction) -> Instruction {
+        Instruction { operation: Operation::Toggle, ..region.clone() }
+    }
+
+    proptest! {
+        // Every case lights up a million lights, so don't try too many
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn toggling_twice_changes_nothing(instructions in prop::collection::vec(instruction(), 0..10), region in instruction()) {
+            let mut toggled = instructions.clone();
+            toggled.extend([toggle(&region), toggle(&region)]);
+            prop_assert!(light_switches(&toggled) == light_switches(&instructions));
+        }
+
+        #[test]
+        fn toggling_adds_two_brightness_a_light(instructions in prop::collection::vec(instruction(), 0..10), region in instruction()) {
+            let mut toggled = instructions.clone();
+            toggled.push(toggle(&region));
+            let lights = (region.region().width() * region.region().height()) as u64;
+            prop_assert_eq!(total_brightness(&toggled), total_brightness(&instructions) + 2 * lights);
+        }
+
+        #[test]
+        fn parses_what_it_prints(i in instruction()) {
+            let operation = match i.operation {
+                Operation::TurnOn => "turn on",
+                Operation::TurnOff => "turn off",
+                Operation::Toggle => "toggle",
+            };
+            let text = format!("{operation} {} through {}", i.start_point, i.end_point);
+            prop_assert_eq!(Instruction::parse(&text), Ok(i));
+        }
     }
 }
@@ -1,20 +1,157 @@
 //! Day 7 - Some Assembly Required
 
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::vec;
+use core::fmt;
+
+/// A circuit that can't be built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A line that isn't a gate, counting lines from one
+    Syntax { line: usize, text: String },
+
+    /// More than one gate drives the same wire
+    Redefined(String),
+
+    /// A gate reads from a wire that nothing drives
+    Undriven(String),
+
+    /// The signal on a wire depends on itself, so it never settles
+    Loop(String),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::Syntax { line, text } => write!(f, "Couldn't understand the gate on line {line}: {text}"),
+            CircuitError::Redefined(wire) => write!(f, "Wire {wire} is driven more than once"),
+            CircuitError::Undriven(wire) => write!(f, "Nothing drives wire {wire}"),
+            CircuitError::Loop(wire) => write!(f, "Wire {wire} is part of a loop"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CircuitError {}
 
 pub struct Circuit<'a> {
-    instructions: HashMap<&'a str, Vec<&'a str>>,
-    memo: HashMap<&'a str, u16>,
+    instructions: BTreeMap<&'a str, Vec<&'a str>>,
+    memo: BTreeMap<&'a str, u16>,
+}
+
+/// Wires are named with lowercase letters
+fn is_wire(token: &str) -> bool {
+    !token.is_empty() && token.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+fn is_operand(token: &str) -> bool {
+    is_wire(token) || token.parse::<u16>().is_ok()
+}
+
+/// The wires and signals a gate reads from
+fn operands<'s, 'a>(steps: &'s [&'a str]) -> impl Iterator<Item = &'a str> + 's {
+    steps.iter().copied().filter(|&step| is_operand(step))
 }
 
 impl<'a> Circuit<'a> {
-    pub fn load(input: &[&'a str]) -> Circuit<'a> {
-        let instructions = input.iter()
-            .map(|line| line.split_once("->").expect("Should have had an arrow delimiter"))
-            .map(|(spec, wire)| (wire.trim(), spec.split_whitespace().collect()))
-            .collect();
+    /// Build a circuit from its gates, one per line, making sure that
+    /// every wire settles on a signal
+    ///
+    /// ```
+    /// # use aoc2015::day7::*;
+    /// assert_eq!(Circuit::load(&["123 -> x", "x AND 7 -> y"]).unwrap().evaluate("y"), 3);
+    ///
+    /// assert_eq!(Circuit::load(&["123 -> x", "x XOR 1 -> y"]).err(), Some(CircuitError::Syntax { line: 2, text: "x XOR 1 -> y".into() }));
+    /// assert_eq!(Circuit::load(&["1 -> x", "2 -> x"]).err(), Some(CircuitError::Redefined("x".into())));
+    /// assert_eq!(Circuit::load(&["NOT y -> x"]).err(), Some(CircuitError::Undriven("y".into())));
+    /// assert!(matches!(Circuit::load(&["NOT y -> x", "x OR 1 -> y"]), Err(CircuitError::Loop(_))));
+    /// ```
+    pub fn load(input: &[&'a str]) -> Result<Circuit<'a>, CircuitError> {
+        let mut instructions = BTreeMap::new();
+        for (index, line) in input.iter().enumerate() {
+            let syntax = || CircuitError::Syntax { line: index + 1, text: line.to_string() };
+            let (spec, wire) = line.split_once("->").ok_or_else(syntax)?;
+            let (wire, steps) = (wire.trim(), spec.split_whitespace().collect::<Vec<_>>());
+
+            let gate = match steps[..] {
+                [input] => is_operand(input),
+                ["NOT", input] => is_operand(input),
+                [lhs, "AND" | "OR" | "LSHIFT" | "RSHIFT", rhs] => is_operand(lhs) && is_operand(rhs),
+                _ => false,
+            };
+
+            if !gate || !is_wire(wire) {
+                return Err(syntax());
+            }
+
+            if instructions.insert(wire, steps).is_some() {
+                return Err(CircuitError::Redefined(wire.to_string()));
+            }
+        }
+
+        let circuit = Self { instructions, memo: BTreeMap::new() };
+        circuit.check_settles()?;
+        Ok(circuit)
+    }
+
+    /// Make sure every wire a gate reads from is driven, and that there
+    /// are no loops
+    fn check_settles(&self) -> Result<(), CircuitError> {
+        // Wires we've started on but not finished are false, and ones
+        // known to settle are true
+        let mut settles: BTreeMap<&str, bool> = BTreeMap::new();
+
+        for &start in self.instructions.keys() {
+            let mut pending = vec![start];
+            while let Some(&wire) = pending.last() {
+                if settles.get(wire) == Some(&true) {
+                    pending.pop();
+                    continue;
+                }
+
+                settles.insert(wire, false);
+                let mut unfinished = Vec::new();
+                for input in operands(&self.instructions[wire]).filter(|&input| is_wire(input)) {
+                    match (self.instructions.contains_key(input), settles.get(input)) {
+                        (false, _) => return Err(CircuitError::Undriven(input.to_string())),
+                        (true, Some(false)) => return Err(CircuitError::Loop(input.to_string())),
+                        (true, Some(true)) => {}
+                        (true, None) => unfinished.push(input),
+                    }
+                }
+
+                match unfinished.is_empty() {
+                    true => {
+                        settles.insert(wire, true);
+                        pending.pop();
+                    }
+                    false => pending.extend(unfinished),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of wires in the circuit
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Every wire with a gate driving it, in no particular order
+    pub fn wires(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.instructions.keys().copied()
+    }
 
-        Self { instructions, memo: HashMap::new() }
+    /// Whether there's a gate driving a wire
+    pub fn drives(&self, wire: &str) -> bool {
+        self.instructions.contains_key(wire)
     }
 
     pub fn reset(&mut self) {
@@ -26,47 +163,60 @@ impl<'a> Circuit<'a> {
         signal
     }
 
+    /// A signal we already know, either because it's a number or because
+    /// it's a wire we've worked out
+    fn known(&self, token: &str) -> Option<u16> {
+        token.parse().ok().or_else(|| self.memo.get(token).copied())
+    }
+
+    /// Work out the signal on a wire (or in a constant). Panics if
+    /// nothing drives the wire
     pub fn evaluate(&mut self, token: &'a str) -> u16 {
-        match token.parse() {
-            Ok(signal) => signal,
-            Err(_) => {
-                match self.memo.get(token) {
-                    Some(signal) => *signal,
-                    None => {
-                        let signal = self.execute(token);
-                        self.solve(token, signal)
-                    }
-                }
+        // Work through the wires that need working out with our own stack,
+        // since a chain of gates can be far longer than the real one
+        let mut pending = vec![token];
+        while let Some(&wire) = pending.last() {
+            if self.known(wire).is_some() {
+                pending.pop();
+                continue;
+            }
+
+            let steps = self.instructions.get(wire).unwrap_or_else(|| panic!("No instructions for wire {wire}"));
+            let unknown = operands(steps).filter(|input| self.known(input).is_none()).collect::<Vec<_>>();
+            if unknown.is_empty() {
+                let signal = self.execute(wire);
+                self.solve(wire, signal);
+                pending.pop();
+            } else {
+                pending.extend(unknown);
             }
         }
+
+        self.known(token).expect("Was just worked out")
     }
 
-    fn execute(&mut self, wire: &'a str) -> u16 {
-        let steps = self.instructions.get(wire).expect("No instructions for wire {wire}").clone();
-        match steps.len() {
-            1 => self.evaluate(steps[0]),
-            2 => {
-                // NOT x
-                if steps[0] != "NOT" {
-                    unimplemented!("The only unary operator we know is NOT");
-                }
+    /// Find the signal on a wire whose inputs are all known
+    fn execute(&self, wire: &str) -> u16 {
+        let steps = &self.instructions[wire];
+        let signal = |token| self.known(token).expect("Inputs should all be known");
+        match steps[..] {
+            [input] => signal(input),
+            ["NOT", input] => !signal(input),
 
-                !self.evaluate(steps[1])
-            },
-            3 => {
-                // A binary operator. Both operands may be a signal constant
-                // or a reference to another wire
-                let lhs = self.evaluate(steps[0]);
-                let rhs = self.evaluate(steps[2]);
-                match steps[1] {
+            // A binary operator. Both operands may be a signal constant
+            // or a reference to another wire. Shifting everything off
+            // the end leaves nothing
+            [lhs, operator, rhs] => {
+                let (lhs, rhs) = (signal(lhs), signal(rhs));
+                match operator {
                     "AND" => lhs & rhs,
                     "OR" => lhs | rhs,
-                    "LSHIFT" => lhs << rhs,
-                    "RSHIFT" => lhs >> rhs,
-                    err => unimplemented!("Binary operand {err} is not supported"),
+                    "LSHIFT" => lhs.checked_shl(rhs.into()).unwrap_or(0),
+                    "RSHIFT" => lhs.checked_shr(rhs.into()).unwrap_or(0),
+                    err => unreachable!("Binary operator {err} should have been caught by load"),
                 }
             },
-            _ => unimplemented!("Unknown instructions: {steps:?}"),
+            _ => unreachable!("Unknown instructions {steps:?} should have been caught by load"),
         }
     }
 }
@@ -74,6 +224,7 @@ impl<'a> Circuit<'a> {
 #[cfg(test)]
 mod answers {
     use super::*;
+    use crate::input::bundled;
     use test_case::test_case;
 
     #[test_case(SAMPLE_INPUT, "x" => 123; "sample data x")]
@@ -86,13 +237,13 @@ mod answers {
     #[test_case(SAMPLE_INPUT, "i" => 65079; "sample data i")]
     #[test_case(personal_input().as_slice(), "a" => 16076; "problem 1 data")]
     pub fn problem1(input: &[&str], target: &str) -> u16 {
-        let mut circuit = Circuit::load(input);
+        let mut circuit = Circuit::load(input).unwrap();
         circuit.evaluate(target)
     }
 
     #[test_case(personal_input().as_slice(), "a" => 2797; "problem 2 data")]
     pub fn problem2(input: &[&str], target: &str) -> u16 {
-        let mut circuit = Circuit::load(input);
+        let mut circuit = Circuit::load(input).unwrap();
         let a = circuit.evaluate(target);
         circuit.reset();
         circuit.solve("b", a);
@@ -101,7 +252,8 @@ mod answers {
     }
 
     fn personal_input() -> Vec<&'static str> {
-        include_str!("./input/day7.txt")
+        bundled(7)
+            .unwrap()
             .lines()
             .map(str::trim)
             .collect()
@@ -118,3 +270,48 @@ mod answers {
         "NOT y -> i",
     ];
 }
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use crate::gen::{self, Netlist};
+    use crate::rng::Rng;
+    use proptest::prelude::*;
+
+    fn netlist() -> impl Strategy<Value = (Vec<String>, Vec<String>)> {
+        (any::<u64>(), 1..30usize, 1..8usize).prop_flat_map(|(seed, depth, width)| {
+            let lines = gen::day7(&mut Rng::new(seed), &Netlist { depth, width })
+                .lines()
+                .map(String::from)
+                .collect::<Vec<_>>();
+
+            (Just(lines.clone()), Just(lines).prop_shuffle())
+        })
+    }
+
+    fn signals(lines: &[String]) -> BTreeMap<&str, u16> {
+        let lines = lines.iter().map(String::as_str).collect::<Vec<_>>();
+        let wires = lines.iter().filter_map(|line| line.split_once("-> ")).map(|(_, wire)| wire).collect::<Vec<_>>();
+
+        let mut circuit = Circuit::load(&lines).unwrap();
+        wires.into_iter().map(|wire| (wire, circuit.evaluate(wire))).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn line_order_doesnt_matter((lines, shuffled) in netlist()) {
+            prop_assert_eq!(signals(&lines), signals(&shuffled));
+        }
+
+        #[test]
+        fn evaluation_order_doesnt_matter((lines, _) in netlist()) {
+            // Working out `a` straight away, or after everything else
+            // has already been remembered, gives the same answer
+            let borrowed = lines.iter().map(String::as_str).collect::<Vec<_>>();
+            let a = Circuit::load(&borrowed).unwrap().evaluate("a");
+
+            let reversed = lines.iter().rev().cloned().collect::<Vec<_>>();
+            prop_assert_eq!(signals(&reversed)["a"], a);
+        }
+    }
+}
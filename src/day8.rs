@@ -0,0 +1,280 @@
+//! Day 8 - Matchsticks
+
+use std::fmt;
+
+use crate::prelude::*;
+use nom::{IResult, branch::alt, bytes::complete::{is_not, take_while_m_n}};
+use nom::character::complete::char;
+use nom::combinator::{all_consuming, consumed, map, map_res, value};
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded};
+
+/// One piece of a string literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// Characters that stand for themselves
+    Text(&'a str),
+
+    /// `\\`, `\"` or `\xHH`, and the byte it stands for
+    Escape(u8),
+}
+
+impl<'a> Token<'a> {
+    /// Parse the next token of a literal, stopping at the closing quote
+    ///
+    /// ```
+    /// # use aoc2015::day8::*;
+    /// assert_eq!(Token::parse("abc\\\"d"), Ok(("\\\"d", Token::Text("abc"))));
+    /// assert_eq!(Token::parse("\\\"d"), Ok(("d", Token::Escape(b'"'))));
+    /// assert_eq!(Token::parse("\\\\"), Ok(("", Token::Escape(b'\\'))));
+    /// assert_eq!(Token::parse("\\x27a"), Ok(("a", Token::Escape(b'\''))));
+    /// assert!(Token::parse("\\x2g").is_err());
+    /// assert!(Token::parse("\"").is_err());
+    /// ```
+    pub fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        let hex = take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit());
+        alt((
+            map(is_not("\\\""), Token::Text),
+            map(
+                preceded(
+                    char('\\'),
+                    alt((
+                        value(b'\\', char('\\')),
+                        value(b'"', char('"')),
+                        preceded(char('x'), map_res(hex, |hex| u8::from_str_radix(hex, 16))),
+                    )),
+                ),
+                Token::Escape,
+            ),
+        ))(input)
+    }
+
+    /// How many bytes of memory the token takes up once decoded
+    pub fn len(&self) -> usize {
+        match self {
+            Token::Text(text) => text.len(),
+            Token::Escape(_) => 1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A line that isn't a valid string literal. Positions are byte offsets
+/// into the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    /// A backslash that doesn't start `\\`, `\"` or `\xHH`
+    InvalidEscape { position: usize },
+
+    /// The line ended before the closing quote
+    Unterminated { position: usize },
+
+    /// Something other than a literal, like a missing opening quote or
+    /// text after the closing one
+    Unexpected { position: usize },
+}
+
+impl LiteralError {
+    pub fn position(&self) -> usize {
+        match self {
+            LiteralError::InvalidEscape { position }
+            | LiteralError::Unterminated { position }
+            | LiteralError::Unexpected { position } => *position,
+        }
+    }
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiteralError::InvalidEscape { position } => write!(f, "Invalid escape sequence at position {position}"),
+            LiteralError::Unterminated { position } => write!(f, "Missing closing quote at position {position}"),
+            LiteralError::Unexpected { position } => write!(f, "Unexpected character at position {position}"),
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+/// A string literal, as written in the code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal<'a> {
+    /// The literal including its quotes, without any surrounding
+    /// whitespace
+    pub code: &'a str,
+
+    /// Everything between the quotes
+    pub tokens: Vec<Token<'a>>,
+}
+
+fn literal(input: &str) -> IResult<&str, (&str, Vec<Token<'_>>)> {
+    consumed(delimited(char('"'), many0(Token::parse), char('"')))(input)
+}
+
+impl<'a> Literal<'a> {
+    /// Parse a whole line as a single literal
+    ///
+    /// ```
+    /// # use aoc2015::day8::*;
+    /// let literal = Literal::parse(" \"a\\x41\\\"\" ").unwrap();
+    /// assert_eq!(literal.code, "\"a\\x41\\\"\"");
+    /// assert_eq!(literal.tokens, [Token::Text("a"), Token::Escape(b'A'), Token::Escape(b'"')]);
+    ///
+    /// assert_eq!(Literal::parse("\"ab\\q\""), Err(LiteralError::InvalidEscape { position: 3 }));
+    /// assert_eq!(Literal::parse("\"ab\\x4\""), Err(LiteralError::InvalidEscape { position: 3 }));
+    /// assert_eq!(Literal::parse("\"abc"), Err(LiteralError::Unterminated { position: 4 }));
+    /// assert_eq!(Literal::parse("\"ab\"c\""), Err(LiteralError::Unexpected { position: 4 }));
+    /// assert_eq!(Literal::parse("abc"), Err(LiteralError::Unexpected { position: 0 }));
+    /// ```
+    pub fn parse(line: &'a str) -> Result<Self, LiteralError> {
+        match all_consuming(ws(literal))(line) {
+            Ok((_, (code, tokens))) => Ok(Literal { code, tokens }),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                let position = line.len() - err.input.len();
+                Err(match err.input.chars().next() {
+                    Some('\\') => LiteralError::InvalidEscape { position },
+                    None => LiteralError::Unterminated { position },
+                    Some(_) => LiteralError::Unexpected { position },
+                })
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("Only complete parsers are used"),
+        }
+    }
+
+    /// How many characters of code the literal takes up
+    pub fn code_len(&self) -> usize {
+        self.code.len()
+    }
+
+    /// How many bytes the decoded string takes up in memory
+    ///
+    /// ```
+    /// # use aoc2015::day8::*;
+    /// assert_eq!(Literal::parse("\"\"").unwrap().memory_len(), 0);
+    /// assert_eq!(Literal::parse("\"aaa\\\"aaa\"").unwrap().memory_len(), 7);
+    /// assert_eq!(Literal::parse("\"\\x27\"").unwrap().memory_len(), 1);
+    /// ```
+    pub fn memory_len(&self) -> usize {
+        self.tokens.iter().map(Token::len).sum()
+    }
+
+    /// How many characters of code it would take to write the literal
+    /// itself as a literal
+    ///
+    /// ```
+    /// # use aoc2015::day8::*;
+    /// assert_eq!(Literal::parse("\"\"").unwrap().encoded_len(), 6);
+    /// assert_eq!(Literal::parse("\"aaa\\\"aaa\"").unwrap().encoded_len(), 16);
+    /// assert_eq!(Literal::parse("\"\\x27\"").unwrap().encoded_len(), 11);
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        encode(self.code).len()
+    }
+
+    /// The bytes the literal stands for
+    ///
+    /// ```
+    /// # use aoc2015::day8::*;
+    /// assert_eq!(Literal::parse("\"a\\\\b\\xff\"").unwrap().decode(), b"a\\b\xff");
+    /// ```
+    pub fn decode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.memory_len());
+        for token in &self.tokens {
+            match token {
+                Token::Text(text) => bytes.extend_from_slice(text.as_bytes()),
+                Token::Escape(byte) => bytes.push(*byte),
+            }
+        }
+
+        bytes
+    }
+}
+
+/// Write text as a string literal the way the puzzle does, escaping only
+/// quotes and backslashes
+///
+/// ```
+/// # use aoc2015::day8::*;
+/// assert_eq!(encode(""), r#""""#);
+/// assert_eq!(encode(r#""abc""#), r#""\"abc\"""#);
+/// assert_eq!(encode("a\\b\n\u{ff}"), "\"a\\\\b\n\u{ff}\"");
+/// ```
+pub fn encode(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// The total characters of code minus the total bytes in memory
+pub fn code_overhead(literals: &[Literal]) -> usize {
+    literals.iter().map(|literal| literal.code_len() - literal.memory_len()).sum()
+}
+
+/// The total characters of the encoded literals minus the total
+/// characters of code
+pub fn encoding_overhead(literals: &[Literal]) -> usize {
+    literals.iter().map(|literal| literal.encoded_len() - literal.code_len()).sum()
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+    use test_case::test_case;
+
+    const SAMPLE_INPUT: &[&str] = &[r#""""#, r#""abc""#, r#""aaa\"aaa""#, r#""\x27""#];
+
+    fn personal_input() -> Vec<&'static str> {
+        bundled(8).unwrap().lines().collect()
+    }
+
+    fn literals<'a>(lines: &[&'a str]) -> Vec<Literal<'a>> {
+        lines.iter().map(|line| Literal::parse(line).unwrap()).collect()
+    }
+
+    #[test_case(SAMPLE_INPUT => 12; "sample data")]
+    #[test_case(personal_input().as_slice() => 1987; "problem 1 data")]
+    pub fn problem1(input: &[&str]) -> usize {
+        code_overhead(&literals(input))
+    }
+
+    #[test_case(SAMPLE_INPUT => 19; "sample data")]
+    #[test_case(personal_input().as_slice() => 2614; "problem 2 data")]
+    pub fn problem2(input: &[&str]) -> usize {
+        encoding_overhead(&literals(input))
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn decodes_what_it_encodes(text in any::<String>()) {
+            let encoded = encode(&text);
+            let literal = Literal::parse(&encoded).unwrap();
+            prop_assert_eq!(literal.memory_len(), text.len());
+            prop_assert_eq!(literal.decode(), text.as_bytes());
+        }
+
+        #[test]
+        fn errors_point_into_the_line(line in r#"[ a\\"x0-9]{0,16}"#) {
+            if let Err(err) = Literal::parse(&line) {
+                prop_assert!(err.position() <= line.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,485 @@
+//! Day 9 - All in a Single Night
+
+use std::fmt;
+
+use crate::prelude::*;
+use nom::{IResult, bytes::complete::tag};
+use nom::character::complete::{alpha1, char, digit1};
+use nom::combinator::{all_consuming, map_res};
+use nom::sequence::tuple;
+
+/// A line of the distances that couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// The line isn't `A to B = N`, or goes from a city to itself
+    Syntax { line: usize, text: String },
+
+    /// Two lines give different distances between the same cities
+    Conflict { line: usize, from: String, to: String },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Syntax { line, text } => write!(f, "Couldn't understand the distance on line {line}: {text}"),
+            GraphError::Conflict { line, from, to } => {
+                write!(f, "Line {line} gives another distance between {from} and {to}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Parse a line like `London to Dublin = 464`
+fn leg(input: &str) -> IResult<&str, (&str, &str, u32)> {
+    tuple((alpha1, ws(tag("to")), alpha1, ws(char('=')), map_res(digit1, str::parse)))(input)
+        .map(|(next, (from, _, to, _, distance))| (next, (from, to, distance)))
+}
+
+/// The distances between cities. Cities are numbered in the order they
+/// first turn up
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    cities: Vec<String>,
+    distances: Vec<Vec<Option<u32>>>,
+}
+
+impl Graph {
+    /// Load `A to B = N` lines, skipping blank ones. Distances go both
+    /// ways, and cities with no line between them can't be travelled
+    /// between directly
+    ///
+    /// ```
+    /// # use aoc2015::day9::*;
+    /// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\n").unwrap();
+    /// assert_eq!(graph.cities().collect::<Vec<_>>(), ["London", "Dublin", "Belfast"]);
+    /// assert_eq!(graph.distance(1, 0), Some(464));
+    /// assert_eq!(graph.distance(1, 2), None);
+    ///
+    /// assert!(matches!(Graph::parse("London to Dublin is 464"), Err(GraphError::Syntax { line: 1, .. })));
+    /// assert!(matches!(Graph::parse("London to Dublin = 1\nDublin to London = 2"), Err(GraphError::Conflict { line: 2, .. })));
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, GraphError> {
+        let mut graph = Graph::default();
+        for (index, text) in text.lines().enumerate().filter(|(_, text)| !text.trim().is_empty()) {
+            let line = index + 1;
+            let (_, (from, to, distance)) = all_consuming(ws(leg))(text)
+                .ok()
+                .filter(|(_, (from, to, _))| from != to)
+                .ok_or_else(|| GraphError::Syntax { line, text: text.trim().to_string() })?;
+
+            let (a, b) = (graph.city(from), graph.city(to));
+            match graph.distances[a][b] {
+                Some(existing) if existing != distance => {
+                    return Err(GraphError::Conflict { line, from: from.to_string(), to: to.to_string() });
+                }
+                _ => {
+                    graph.distances[a][b] = Some(distance);
+                    graph.distances[b][a] = Some(distance);
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// The number for a city, adding it if it's new
+    fn city(&mut self, name: &str) -> usize {
+        if let Some(index) = self.cities.iter().position(|city| city == name) {
+            return index;
+        }
+
+        self.cities.push(name.to_string());
+        self.distances.iter_mut().for_each(|row| row.push(None));
+        self.distances.push(vec![None; self.cities.len()]);
+        self.cities.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.cities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cities.is_empty()
+    }
+
+    pub fn cities(&self) -> impl Iterator<Item = &str> {
+        self.cities.iter().map(String::as_str)
+    }
+
+    pub fn name(&self, city: usize) -> &str {
+        &self.cities[city]
+    }
+
+    /// How far it is directly from one city to another, if there's a way
+    pub fn distance(&self, from: usize, to: usize) -> Option<u32> {
+        self.distances[from][to]
+    }
+
+    /// How far it is to visit the cities in order, if every leg can be
+    /// travelled. Each leg fits in a `u32`, so the total always fits in a
+    /// `u64`
+    pub fn path_distance(&self, stops: &[usize]) -> Option<u64> {
+        stops.windows(2).try_fold(0, |total, leg| Some(total + u64::from(self.distance(leg[0], leg[1])?)))
+    }
+
+    fn route(&self, stops: &[usize], distance: u64) -> Route<'_> {
+        Route { stops: stops.iter().map(|&city| self.name(city)).collect(), distance, exact: true }
+    }
+}
+
+/// Whether Santa wants the shortest or the longest way round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Shortest,
+    Longest,
+}
+
+impl Goal {
+    /// Whether distance `a` is better than distance `b`
+    fn prefers(self, a: u64, b: u64) -> bool {
+        match self {
+            Goal::Shortest => a < b,
+            Goal::Longest => a > b,
+        }
+    }
+}
+
+/// A way of visiting every city exactly once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route<'a> {
+    pub stops: Vec<&'a str>,
+    pub distance: u64,
+
+    /// Whether this is known to be the best route, rather than just a
+    /// good one
+    pub exact: bool,
+}
+
+impl fmt::Display for Route<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.stops.join(" -> "), self.distance)?;
+        match self.exact {
+            true => Ok(()),
+            false => write!(f, " (not exact)"),
+        }
+    }
+}
+
+/// The most cities [`held_karp`] will take on, which is 18. Every extra
+/// city doubles the memory it needs, and this many already takes about
+/// 40 megabytes. Anything bigger is left to [`approximate`]
+pub const HELD_KARP_LIMIT: usize = 18;
+
+/// Marks a set of cities and a last city that no path has reached yet
+const UNREACHED: u64 = u64::MAX;
+
+/// Find the best route with the Held-Karp algorithm, building up the best
+/// path through every subset of the cities ending at each city. That's
+/// `O(2ⁿ n²)` time and `O(2ⁿ n)` memory, so it gives up on more than the
+/// 18 cities in [`HELD_KARP_LIMIT`]. There's no route if there aren't any
+/// cities, or they can't all be visited
+///
+/// ```
+/// # use aoc2015::day9::*;
+/// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+/// let route = held_karp(&graph, Goal::Shortest).unwrap();
+/// assert_eq!(route.to_string(), "Belfast -> Dublin -> London = 605");
+/// assert_eq!(held_karp(&graph, Goal::Longest).unwrap().distance, 982);
+///
+/// let graph = Graph::parse("London to Dublin = 464\nBelfast to Paris = 1").unwrap();
+/// assert_eq!(held_karp(&graph, Goal::Shortest), None);
+///
+/// let graph = Graph::parse("A to B = 4000000000\nB to C = 4000000000\nA to C = 1").unwrap();
+/// assert_eq!(held_karp(&graph, Goal::Longest).unwrap().distance, 8000000000);
+/// ```
+pub fn held_karp(graph: &Graph, goal: Goal) -> Option<Route<'_>> {
+    let cities = graph.len();
+    if cities == 0 || cities > HELD_KARP_LIMIT {
+        return None;
+    }
+
+    // The best distance through the cities in each set, ending at each
+    // city, all in one table with a row of cities for each set
+    let all = (1 << cities) - 1;
+    let mut best = vec![UNREACHED; (all + 1) * cities];
+    for city in 0..cities {
+        best[(1 << city) * cities + city] = 0;
+    }
+
+    for visited in 1..=all {
+        for last in 0..cities {
+            let so_far = best[visited * cities + last];
+            if so_far == UNREACHED {
+                continue;
+            }
+
+            for next in (0..cities).filter(|next| visited & (1 << next) == 0) {
+                let Some(leg) = graph.distance(last, next) else {
+                    continue;
+                };
+
+                let distance = so_far + u64::from(leg);
+                let entry = &mut best[(visited | (1 << next)) * cities + next];
+                if *entry == UNREACHED || goal.prefers(distance, *entry) {
+                    *entry = distance;
+                }
+            }
+        }
+    }
+
+    let (mut city, distance) = (0..cities)
+        .map(|last| (last, best[all * cities + last]))
+        .filter(|&(_, distance)| distance != UNREACHED)
+        .reduce(|a, b| if goal.prefers(b.1, a.1) { b } else { a })?;
+
+    // Follow the cities back to the start, looking for a city before each
+    // one that the best distance could have come from
+    let mut stops = vec![city];
+    let mut visited = all;
+    let mut remaining = distance;
+    while visited.count_ones() > 1 {
+        visited &= !(1 << city);
+        let previous = (0..cities)
+            .filter(|previous| visited & (1 << previous) != 0)
+            .find(|&previous| {
+                let so_far = best[visited * cities + previous];
+                let leg = graph.distance(previous, city).map(u64::from);
+                so_far != UNREACHED && leg.is_some_and(|leg| so_far + leg == remaining)
+            })
+            .expect("Every step back was reached");
+
+        remaining = best[visited * cities + previous];
+        city = previous;
+        stops.push(city);
+    }
+
+    stops.reverse();
+    Some(graph.route(&stops, distance))
+}
+
+/// Find a good route quickly, for when there are too many cities for
+/// [`held_karp`]. Starting from each city in turn, it greedily heads for
+/// the nearest city not visited yet (or the farthest, for the longest
+/// route). Then the best of those is improved with 2-opt, reversing
+/// stretches of the route for as long as that makes it better. That's
+/// `O(n³)` for each pass, but there's no telling whether the route is the
+/// best one, so it isn't marked as exact. The greedy routes can get stuck
+/// when some cities aren't connected, so there might not be a route even
+/// though one exists
+///
+/// ```
+/// # use aoc2015::day9::*;
+/// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+/// let route = approximate(&graph, Goal::Shortest).unwrap();
+/// assert_eq!(route.to_string(), "London -> Dublin -> Belfast = 605 (not exact)");
+/// assert_eq!(approximate(&graph, Goal::Longest).unwrap().distance, 982);
+///
+/// let graph = Graph::parse("London to Dublin = 464\nBelfast to Paris = 1").unwrap();
+/// assert_eq!(approximate(&graph, Goal::Shortest), None);
+/// ```
+pub fn approximate(graph: &Graph, goal: Goal) -> Option<Route<'_>> {
+    let cities = graph.len();
+    let leg = |from: usize, to: usize| graph.distance(from, to).map(u64::from);
+
+    let greedy = |start: usize| {
+        let mut stops = vec![start];
+        let mut visited = vec![false; cities];
+        visited[start] = true;
+        while stops.len() < cities {
+            let last = stops[stops.len() - 1];
+            let (next, _) = (0..cities)
+                .filter(|&next| !visited[next])
+                .filter_map(|next| Some((next, leg(last, next)?)))
+                .reduce(|a, b| if goal.prefers(b.1, a.1) { b } else { a })?;
+
+            visited[next] = true;
+            stops.push(next);
+        }
+
+        Some(stops)
+    };
+
+    let mut stops = (0..cities)
+        .filter_map(greedy)
+        .filter_map(|stops| Some((graph.path_distance(&stops)?, stops)))
+        .reduce(|a, b| if goal.prefers(b.0, a.0) { b } else { a })
+        .map(|(_, stops)| stops)?;
+
+    // Reversing `stops[i..=j]` only changes the legs at either end of it,
+    // which aren't there at all at the ends of the route
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..cities {
+            for j in i + 1..cities {
+                let before = i.checked_sub(1).map(|before| stops[before]);
+                let after = stops.get(j + 1).copied();
+                let ends = |first: usize, last: usize| {
+                    let into = before.map_or(Some(0), |before| leg(before, first))?;
+                    let out = after.map_or(Some(0), |after| leg(last, after))?;
+                    Some(into + out)
+                };
+
+                let Some(current) = ends(stops[i], stops[j]) else {
+                    continue;
+                };
+                if ends(stops[j], stops[i]).is_some_and(|reversed| goal.prefers(reversed, current)) {
+                    stops[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+
+    let distance = graph.path_distance(&stops).expect("Reversals only ever use legs that are there");
+    Some(Route { exact: false, ..graph.route(&stops, distance) })
+}
+
+/// Find the best route by trying every order of the cities, for checking
+/// [`held_karp`] against. That's `O(n!)`, so only use it on a handful of
+/// cities
+///
+/// ```
+/// # use aoc2015::day9::*;
+/// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+/// assert_eq!(brute_force(&graph, Goal::Shortest).unwrap().distance, 605);
+/// assert_eq!(brute_force(&graph, Goal::Longest).unwrap().to_string(), "Dublin -> London -> Belfast = 982");
+/// ```
+pub fn brute_force(graph: &Graph, goal: Goal) -> Option<Route<'_>> {
+    let mut order = (0..graph.len()).collect::<Vec<_>>();
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut consider = |order: &[usize]| {
+        if let Some(distance) = graph.path_distance(order).filter(|_| !order.is_empty()) {
+            match &best {
+                Some((current, _)) if !goal.prefers(distance, *current) => {}
+                _ => best = Some((distance, order.to_vec())),
+            }
+        }
+    };
+
+    // Heap's algorithm, which gets to each order by swapping just two
+    // cities from the one before
+    let mut counters = vec![0; order.len()];
+    consider(&order);
+    let mut index = 1;
+    while index < order.len() {
+        if counters[index] < index {
+            let other = if index % 2 == 0 { 0 } else { counters[index] };
+            order.swap(other, index);
+            consider(&order);
+            counters[index] += 1;
+            index = 1;
+        } else {
+            counters[index] = 0;
+            index += 1;
+        }
+    }
+
+    best.map(|(distance, stops)| graph.route(&stops, distance))
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::input::bundled;
+    use test_case::test_case;
+
+    const SAMPLE_INPUT: &str = "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141";
+
+    #[test_case(SAMPLE_INPUT => 605; "sample data")]
+    #[test_case(bundled(9).unwrap() => 147; "problem 1 data")]
+    pub fn problem1(input: &str) -> u64 {
+        held_karp(&Graph::parse(input).unwrap(), Goal::Shortest).unwrap().distance
+    }
+
+    #[test_case(SAMPLE_INPUT => 982; "sample data")]
+    #[test_case(bundled(9).unwrap() => 787; "problem 2 data")]
+    pub fn problem2(input: &str) -> u64 {
+        held_karp(&Graph::parse(input).unwrap(), Goal::Longest).unwrap().distance
+    }
+
+    #[test]
+    pub fn brute_force_agrees() {
+        let graph = Graph::parse(bundled(9).unwrap()).unwrap();
+        for goal in [Goal::Shortest, Goal::Longest] {
+            assert_eq!(brute_force(&graph, goal).unwrap().distance, held_karp(&graph, goal).unwrap().distance);
+        }
+    }
+
+    #[test]
+    pub fn approximate_routes_are_good_enough_here() {
+        let graph = Graph::parse(bundled(9).unwrap()).unwrap();
+        let shortest = approximate(&graph, Goal::Shortest).unwrap();
+        assert_eq!((shortest.distance, shortest.exact), (147, false));
+        assert_eq!(approximate(&graph, Goal::Longest).unwrap().distance, 787);
+    }
+
+    #[test]
+    pub fn long_legs_dont_hide_the_best_route() {
+        let graph = Graph::parse(
+            "P to C = 1000000000\nC to A = 3000000000\nC to B = 1\nA to B = 1\nB to X = 1\nA to X = 1\nX to Q = 1000000000",
+        )
+        .unwrap();
+        let route = held_karp(&graph, Goal::Shortest).unwrap();
+        assert_eq!(route.to_string(), "Q -> X -> A -> B -> C -> P = 2000000003");
+        for goal in [Goal::Shortest, Goal::Longest] {
+            assert_eq!(held_karp(&graph, goal).unwrap().distance, brute_force(&graph, goal).unwrap().distance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    const CITIES: &[&str] = &["Ashby", "Bree", "Crickhollow", "Dale", "Esgaroth", "Fornost", "Gondolin"];
+
+    /// Distances between some of the first few cities, with some missing
+    fn graph() -> impl Strategy<Value = Graph> {
+        (1..=CITIES.len()).prop_flat_map(|cities| {
+            let pairs = cities * (cities - 1) / 2;
+            prop::collection::vec(prop::option::weighted(0.9, 1..100u32), pairs).prop_map(move |distances| {
+                let pairs = (0..cities).flat_map(|a| (a + 1..cities).map(move |b| (a, b)));
+                let lines = pairs
+                    .zip(distances)
+                    .filter_map(|((a, b), distance)| Some(format!("{} to {} = {}", CITIES[a], CITIES[b], distance?)))
+                    .collect::<Vec<_>>();
+                Graph::parse(&lines.join("\n")).unwrap()
+            })
+        })
+    }
+
+    fn is_route(graph: &Graph, route: &Route) -> bool {
+        let stops = route.stops.iter().map(|stop| graph.cities().position(|city| city == *stop).unwrap()).collect::<Vec<_>>();
+        let mut sorted = stops.clone();
+        sorted.sort();
+        sorted == (0..graph.len()).collect::<Vec<_>>() && graph.path_distance(&stops) == Some(route.distance)
+    }
+
+    proptest! {
+        #[test]
+        fn held_karp_matches_brute_force(graph in graph(), longest in any::<bool>()) {
+            let goal = if longest { Goal::Longest } else { Goal::Shortest };
+            let exact = held_karp(&graph, goal);
+            let reference = brute_force(&graph, goal);
+            prop_assert_eq!(exact.as_ref().map(|route| route.distance), reference.as_ref().map(|route| route.distance));
+
+            for route in exact.iter().chain(&reference) {
+                prop_assert!(is_route(&graph, route), "{}", route);
+            }
+        }
+
+        #[test]
+        fn approximate_routes_are_never_better(graph in graph(), longest in any::<bool>()) {
+            let goal = if longest { Goal::Longest } else { Goal::Shortest };
+            if let Some(route) = approximate(&graph, goal) {
+                prop_assert!(is_route(&graph, &route), "{}", route);
+                let best = brute_force(&graph, goal).unwrap();
+                prop_assert!(!goal.prefers(route.distance, best.distance), "{} beats {}", route, best);
+            }
+        }
+    }
+}
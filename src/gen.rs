@@ -0,0 +1,427 @@
+//! Making up puzzle inputs, for trying the solutions on more than the
+//! bundled inputs
+//!
+//! Everything is generated from a seeded [`Rng`], so the same seed always
+//! gives the same input, in exactly the format the puzzle uses.
+
+use std::fmt::Write;
+
+use crate::rng::Rng;
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Somewhere for Santa to go on day 9
+const PLACES: &[&str] = &["Faerun", "Norrath", "Tristram", "AlphaCentauri", "Arbre", "Snowdin", "Tambi", "Straylight"];
+
+/// The strings in day 12's documents, which are all colours
+const COLOURS: &[&str] = &["red", "green", "blue", "orange", "yellow", "violet"];
+
+/// Day 1: a string of parentheses that goes into the basement at some
+/// point, since part two needs it to
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day1::*;
+/// let input = gen::day1(&mut Rng::new(1), 1000);
+/// assert!(input.len() >= 1000);
+/// assert!(input.chars().all(|c| c == '(' || c == ')'));
+/// assert!(find_basement(&input).unwrap() <= input.len());
+/// ```
+pub fn day1(rng: &mut Rng, length: usize) -> String {
+    let mut floor = 0i64;
+    let mut lowest = 0;
+    let mut input = String::with_capacity(length + 1);
+    for _ in 0..length {
+        let up = rng.below(2) == 0;
+        input.push(if up { '(' } else { ')' });
+        floor += if up { 1 } else { -1 };
+        lowest = lowest.min(floor);
+    }
+
+    // Wander down to the basement if we never got there
+    if lowest >= 0 {
+        input.extend(std::iter::repeat(')').take(floor as usize + 1));
+    }
+
+    input
+}
+
+/// Day 2: one `LxWxH` present per line, with sides from 1 to `max_side`
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day2::*;
+/// let input = gen::day2(&mut Rng::new(2), 100, 30);
+/// let presents = input.lines().map(|line| line.parse::<Present>().unwrap()).collect::<Vec<_>>();
+/// assert_eq!(presents.len(), 100);
+/// assert!(presents.iter().all(|p| (1..=30).contains(&p.l) && (1..=30).contains(&p.h)));
+/// ```
+///
+/// # Panics
+///
+/// If `max_side` is zero, since every side is at least 1
+pub fn day2(rng: &mut Rng, count: usize, max_side: u32) -> String {
+    lines(count, || {
+        let [l, w, h] = [(); 3].map(|_| rng.between(1, u64::from(max_side)));
+        format!("{l}x{w}x{h}")
+    })
+}
+
+/// Day 3: a stream of `^v<>` moves
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// let input = gen::day3(&mut Rng::new(3), 500);
+/// assert_eq!(input.len(), 500);
+/// assert!(input.chars().all(|c| "^v<>".contains(c)));
+/// ```
+pub fn day3(rng: &mut Rng, length: usize) -> String {
+    (0..length).map(|_| *rng.pick(&['^', 'v', '<', '>'])).collect()
+}
+
+/// Day 4: a secret key of lowercase letters
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// let key = gen::day4(&mut Rng::new(4), 8);
+/// assert_eq!(key.len(), 8);
+/// assert!(key.bytes().all(|b| b.is_ascii_lowercase()));
+/// ```
+pub fn day4(rng: &mut Rng, length: usize) -> String {
+    word(rng, length)
+}
+
+/// Day 5: one lowercase word per line
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// let input = gen::day5(&mut Rng::new(5), 1000, 16);
+/// assert_eq!(input.lines().count(), 1000);
+/// assert!(input.lines().all(|line| line.len() == 16 && line.bytes().all(|b| b.is_ascii_lowercase())));
+/// ```
+pub fn day5(rng: &mut Rng, count: usize, length: usize) -> String {
+    lines(count, || word(rng, length))
+}
+
+/// Day 6: `turn on`, `turn off` and `toggle` instructions for rectangles
+/// somewhere on a `size` by `size` grid
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day6::*;
+/// let input = gen::day6(&mut Rng::new(6), 300, 1000);
+/// let instructions = input.lines().map(|line| Instruction::parse(line).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(instructions.len(), 300);
+/// assert!(instructions.iter().all(|i| i.start_point.x <= i.end_point.x && i.end_point.y < 1000));
+/// ```
+///
+/// # Panics
+///
+/// If `size` is zero and there are instructions to make, since they'd
+/// have nowhere to go
+pub fn day6(rng: &mut Rng, count: usize, size: u64) -> String {
+    lines(count, || {
+        let operation = *rng.pick(&["turn on", "turn off", "toggle"]);
+        let [x1, x2, y1, y2] = [(); 4].map(|_| rng.below(size));
+        format!("{operation} {},{} through {},{}", x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))
+    })
+}
+
+/// The shape of a Day 7 circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Netlist {
+    /// How many gates a signal goes through, at most, on its way to `a`
+    pub depth: usize,
+
+    /// How many wires there are at each depth
+    pub width: usize,
+}
+
+impl Default for Netlist {
+    fn default() -> Self {
+        Self { depth: 40, width: 8 }
+    }
+}
+
+/// Day 7: a circuit of gates that always settles, in no particular order.
+/// It's built up in layers, with every gate only reading from wires in
+/// earlier layers. Wire `b` is one of the inputs, and wire `a` is the
+/// single wire in the last layer
+///
+/// ```
+/// # use aoc2015::gen::{self, Netlist};
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day7::*;
+/// let input = gen::day7(&mut Rng::new(7), &Netlist { depth: 10, width: 4 });
+/// let lines = input.lines().collect::<Vec<_>>();
+/// assert_eq!(lines.len(), 9 * 4 + 1);
+///
+/// let mut circuit = Circuit::load(&lines).unwrap();
+/// circuit.evaluate("a");
+/// ```
+pub fn day7(rng: &mut Rng, netlist: &Netlist) -> String {
+    let width = netlist.width.max(1);
+    let depth = netlist.depth.max(1);
+
+    // Leave `a` and `b` for the wires the puzzle cares about
+    let mut names = (0..).map(wire_name).filter(|name| name != "a" && name != "b");
+    let mut layers: Vec<Vec<String>> = Vec::new();
+    let mut gates = Vec::new();
+
+    for layer in 0..depth {
+        let last = layer + 1 == depth;
+        let count = if last { 1 } else { width };
+        let mut wires = Vec::with_capacity(count);
+
+        for index in 0..count {
+            let wire = match (layer, index, last) {
+                (_, _, true) => "a".to_string(),
+                (0, 0, _) => "b".to_string(),
+                _ => names.next().expect("There are always more names"),
+            };
+
+            let gate = match layers.last() {
+                None => rng.below(1 << 16).to_string(),
+                Some(previous) => {
+                    // Reading from the previous layer keeps the circuit as
+                    // deep as asked, and anything earlier is fair game too
+                    let input = rng.pick(previous).clone();
+                    let earlier = rng.pick(&layers);
+                    let other = rng.pick(earlier).clone();
+                    // Mostly gates that keep plenty of bits set, so the
+                    // signal on `a` doesn't just fade away to nothing
+                    match rng.below(10) {
+                        0 => format!("{input} AND {other}"),
+                        1 => format!("1 AND {input}"),
+                        2..=4 => format!("{input} OR {other}"),
+                        5 => format!("{input} LSHIFT {}", rng.between(1, 15)),
+                        6 => format!("{input} RSHIFT {}", rng.between(1, 15)),
+                        _ => format!("NOT {input}"),
+                    }
+                }
+            };
+
+            gates.push(format!("{gate} -> {wire}"));
+            wires.push(wire);
+        }
+
+        layers.push(wires);
+    }
+
+    rng.shuffle(&mut gates);
+    gates.join("\n")
+}
+
+/// Day 8: one string literal per line, of lowercase letters mixed with
+/// `\\`, `\"` and `\xHH` escapes
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day8::*;
+/// let input = gen::day8(&mut Rng::new(8), 300);
+/// let literals = input.lines().map(|line| Literal::parse(line).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(literals.len(), 300);
+/// assert!(literals.iter().any(|literal| literal.memory_len() < literal.code_len()));
+/// ```
+pub fn day8(rng: &mut Rng, count: usize) -> String {
+    lines(count, || {
+        let mut literal = String::from("\"");
+        for _ in 0..rng.between(1, 30) {
+            match rng.below(50) {
+                0..=2 => literal.push_str("\\\\"),
+                3..=5 => literal.push_str("\\\""),
+                6..=8 => write!(literal, "\\x{:02x}", rng.below(256)).expect("Writing to a string can't fail"),
+                _ => literal.push(char::from(*rng.pick(LETTERS))),
+            }
+        }
+        literal.push('"');
+        literal
+    })
+}
+
+/// Day 9: the distance between every pair of `cities` cities
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day9::*;
+/// let graph = Graph::parse(&gen::day9(&mut Rng::new(9), 10)).unwrap();
+/// assert_eq!(graph.len(), 10);
+/// assert!(held_karp(&graph, Goal::Shortest).is_some());
+/// ```
+pub fn day9(rng: &mut Rng, cities: usize) -> String {
+    let names = (0..cities)
+        .map(|index| match PLACES.get(index) {
+            Some(place) => place.to_string(),
+            None => format!("Place{}", wire_name(index)),
+        })
+        .collect::<Vec<_>>();
+
+    let pairs = (0..cities).flat_map(|a| (a + 1..cities).map(move |b| (a, b))).collect::<Vec<_>>();
+    lines(pairs.len(), {
+        let mut pairs = pairs.into_iter();
+        move || {
+            let (a, b) = pairs.next().expect("There's a line for every pair");
+            format!("{} to {} = {}", names[a], names[b], rng.between(3, 150))
+        }
+    })
+}
+
+/// Day 10: a seed of 1s, 2s and 3s
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// let seed = gen::day10(&mut Rng::new(10), 10);
+/// assert_eq!(seed.len(), 10);
+/// assert!(seed.bytes().all(|b| (b'1'..=b'3').contains(&b)));
+/// ```
+pub fn day10(rng: &mut Rng, length: usize) -> String {
+    (0..length).map(|_| *rng.pick(&['1', '2', '3'])).collect()
+}
+
+/// Day 11: a password of lowercase letters
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day11::*;
+/// let password = gen::day11(&mut Rng::new(11), 8);
+/// assert_eq!(parse_password(&password).unwrap().len(), 8);
+/// ```
+pub fn day11(rng: &mut Rng, length: usize) -> String {
+    word(rng, length)
+}
+
+/// Day 12: a document of nested arrays and objects, `depth` levels deep
+/// at most, with some of the objects red
+///
+/// ```
+/// # use aoc2015::gen;
+/// # use aoc2015::rng::Rng;
+/// # use aoc2015::day12::*;
+/// let document = Value::parse(&gen::day12(&mut Rng::new(12), 5)).unwrap();
+/// assert!(matches!(document, Value::Array(_)));
+/// ```
+pub fn day12(rng: &mut Rng, depth: usize) -> String {
+    let mut document = String::new();
+    json_array(rng, depth, &mut document);
+    document
+}
+
+fn json_value(rng: &mut Rng, depth: usize, out: &mut String) {
+    match rng.below(if depth == 0 { 2 } else { 4 }) {
+        0 => write!(out, "{}", rng.between(0, 250) as i64 - 50).expect("Writing to a string can't fail"),
+        1 => write!(out, "\"{}\"", rng.pick(COLOURS)).expect("Writing to a string can't fail"),
+        2 => json_array(rng, depth - 1, out),
+        _ => json_object(rng, depth - 1, out),
+    }
+}
+
+fn json_array(rng: &mut Rng, depth: usize, out: &mut String) {
+    out.push('[');
+    for index in 0..rng.between(0, 5) {
+        if index > 0 {
+            out.push(',');
+        }
+        json_value(rng, depth, out);
+    }
+    out.push(']');
+}
+
+fn json_object(rng: &mut Rng, depth: usize, out: &mut String) {
+    out.push('{');
+    for index in 0..rng.between(0, 5) {
+        if index > 0 {
+            out.push(',');
+        }
+        write!(out, "\"{}\":", char::from(LETTERS[index as usize])).expect("Writing to a string can't fail");
+        json_value(rng, depth, out);
+    }
+    out.push('}');
+}
+
+/// The names of wires, in order: `a` to `z`, then `aa` to `zz` and so on
+fn wire_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(LETTERS[index % 26]);
+        index /= 26;
+        if index == 0 {
+            break;
+        }
+        index -= 1;
+    }
+
+    name.reverse();
+    String::from_utf8(name).expect("Wire names are ASCII")
+}
+
+fn word(rng: &mut Rng, length: usize) -> String {
+    (0..length).map(|_| char::from(*rng.pick(LETTERS))).collect()
+}
+
+fn lines(count: usize, mut line: impl FnMut() -> String) -> String {
+    let mut text = String::new();
+    for index in 0..count {
+        if index > 0 {
+            text.push('\n');
+        }
+        write!(text, "{}", line()).expect("Writing to a string can't fail");
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::day;
+
+    #[test]
+    pub fn wire_names() {
+        assert_eq!(wire_name(0), "a");
+        assert_eq!(wire_name(25), "z");
+        assert_eq!(wire_name(26), "aa");
+        assert_eq!(wire_name(26 + 26 * 26), "aaa");
+    }
+
+    #[test]
+    pub fn same_seed_same_input() {
+        assert_eq!(day7(&mut Rng::new(9), &Netlist::default()), day7(&mut Rng::new(9), &Netlist::default()));
+        assert_ne!(day5(&mut Rng::new(9), 10, 16), day5(&mut Rng::new(10), 10, 16));
+    }
+
+    #[test]
+    pub fn solutions_run_on_generated_inputs() {
+        for seed in 0..5 {
+            let mut rng = Rng::new(seed);
+            let inputs = [
+                (1, day1(&mut rng, 5000)),
+                (2, day2(&mut rng, 1000, 30)),
+                (3, day3(&mut rng, 5000)),
+                (5, day5(&mut rng, 1000, 16)),
+                (6, day6(&mut rng, 50, 1000)),
+                (7, day7(&mut rng, &Netlist { depth: 100, width: 10 })),
+                (8, day8(&mut rng, 300)),
+                (9, day9(&mut rng, 8)),
+                (10, day10(&mut rng, 10)),
+                (11, day11(&mut rng, 8)),
+                (12, day12(&mut rng, 5)),
+            ];
+
+            for (number, input) in inputs {
+                let day = day(number).unwrap();
+                assert!((day.part1)(&input).is_ok(), "Day {number} part 1, seed {seed}");
+                assert!((day.part2)(&input).is_ok(), "Day {number} part 2, seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Coordinates, directions and grids shared between the days that
+//! walk around on a 2D plane
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Sub};
+
+/// A position on the plane. Positive `y` is north
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Pos {
+    pub const ORIGIN: Pos = Pos { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The position one step away in the given direction
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// assert_eq!(Pos::ORIGIN.step(Dir::North), Pos::new(0, 1));
+    /// assert_eq!(Pos::new(3, 3).step(Dir::West), Pos::new(2, 3));
+    /// ```
+    pub fn step(self, dir: Dir) -> Self {
+        self + dir.offset()
+    }
+
+    /// The four positions sharing an edge with this one
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let n = Pos::ORIGIN.neighbours().collect::<Vec<_>>();
+    /// assert_eq!(n.len(), 4);
+    /// assert!(n.contains(&Pos::new(0, -1)));
+    /// ```
+    pub fn neighbours(self) -> impl Iterator<Item = Pos> {
+        Dir::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// The eight positions sharing an edge or a corner with this one
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let n = Pos::ORIGIN.surrounding().collect::<Vec<_>>();
+    /// assert_eq!(n.len(), 8);
+    /// assert!(n.contains(&Pos::new(-1, 1)));
+    /// assert!(!n.contains(&Pos::ORIGIN));
+    /// ```
+    pub fn surrounding(self) -> impl Iterator<Item = Pos> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Pos::new(dx, dy)))
+            .filter(|&offset| offset != Pos::ORIGIN)
+            .map(move |offset| self + offset)
+    }
+
+    /// The taxicab distance between two positions
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// assert_eq!(Pos::new(1, -2).manhattan(Pos::new(-2, 2)), 7);
+    /// ```
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Self::Output {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Pos) -> Self::Output {
+        Pos::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl From<(isize, isize)> for Pos {
+    fn from(value: (isize, isize)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// One of the four compass directions
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    /// The change in position from taking one step this way
+    pub fn offset(self) -> Pos {
+        match self {
+            Dir::North => Pos::new(0, 1),
+            Dir::East => Pos::new(1, 0),
+            Dir::South => Pos::new(0, -1),
+            Dir::West => Pos::new(-1, 0),
+        }
+    }
+
+    /// Parse a direction from an arrow (`^v<>`) only, the way elf HQ
+    /// writes them
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// assert_eq!(Dir::from_arrow('>'), Ok(Dir::East));
+    /// assert_eq!(Dir::from_arrow('e'), Err(UnknownDir('e')));
+    /// ```
+    pub fn from_arrow(value: char) -> Result<Self, UnknownDir> {
+        match value {
+            '^' | '>' | 'v' | '<' => Dir::try_from(value),
+            err => Err(UnknownDir(err)),
+        }
+    }
+}
+
+/// A character that doesn't name a direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDir(pub char);
+
+impl fmt::Display for UnknownDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Don't know what direction a {} is", self.0)
+    }
+}
+
+impl std::error::Error for UnknownDir {}
+
+impl TryFrom<char> for Dir {
+    type Error = UnknownDir;
+
+    /// Parse a direction from either an arrow (`^v<>`) or a
+    /// compass letter
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// assert_eq!(Dir::try_from('^'), Ok(Dir::North));
+    /// assert_eq!(Dir::try_from('v'), Ok(Dir::South));
+    /// assert_eq!(Dir::try_from('E'), Ok(Dir::East));
+    /// assert_eq!(Dir::try_from('w'), Ok(Dir::West));
+    /// assert_eq!(Dir::try_from('x'), Err(UnknownDir('x')));
+    /// ```
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' | 'n' => Ok(Dir::North),
+            '>' | 'E' | 'e' => Ok(Dir::East),
+            'v' | 'S' | 's' => Ok(Dir::South),
+            '<' | 'W' | 'w' => Ok(Dir::West),
+            err => Err(UnknownDir(err)),
+        }
+    }
+}
+
+/// An inclusive rectangle of positions
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    /// The rectangle between two opposite corners
+    pub fn new(a: Pos, b: Pos) -> Self {
+        Self {
+            min: Pos::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Pos::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle containing every one of the positions,
+    /// if there are any
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let b = Bounds::of([Pos::new(1, 5), Pos::new(-2, 3), Pos::new(0, 0)]).unwrap();
+    /// assert_eq!(b, Bounds { min: Pos::new(-2, 0), max: Pos::new(1, 5) });
+    /// assert_eq!((b.width(), b.height()), (4, 6));
+    /// assert!(Bounds::of([]).is_none());
+    /// ```
+    pub fn of(positions: impl IntoIterator<Item = Pos>) -> Option<Self> {
+        positions
+            .into_iter()
+            .fold(None, |bounds: Option<Bounds>, pos| match bounds {
+                None => Some(Bounds::new(pos, pos)),
+                Some(b) => Some(Bounds::new(
+                    Pos::new(b.min.x.min(pos.x), b.min.y.min(pos.y)),
+                    Pos::new(b.max.x.max(pos.x), b.max.y.max(pos.y)),
+                )),
+            })
+    }
+
+    pub fn width(&self) -> usize {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+
+    /// Every position in the rectangle, a row at a time
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let b = Bounds::new(Pos::new(0, 0), Pos::new(1, 2));
+    /// assert_eq!(b.positions().count(), 6);
+    /// assert_eq!(b.positions().nth(2), Some(Pos::new(0, 1)));
+    /// ```
+    pub fn positions(self) -> impl Iterator<Item = Pos> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Pos::new(x, y)))
+    }
+}
+
+/// A grid that only stores the positions it has been told about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self { cells: HashMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn entry(&mut self, pos: Pos) -> Entry<'_, Pos, T> {
+        self.cells.entry(pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// The smallest rectangle containing everything in the grid
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let mut grid = SparseGrid::new();
+    /// assert_eq!(grid.bounds(), None);
+    ///
+    /// grid.insert(Pos::new(2, -1), 'a');
+    /// grid.insert(Pos::new(-1, 4), 'b');
+    /// assert_eq!(grid.bounds(), Some(Bounds::new(Pos::new(-1, -1), Pos::new(2, 4))));
+    /// ```
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::of(self.positions())
+    }
+}
+
+impl<T> Index<Pos> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("Nothing at {pos}"))
+    }
+}
+
+impl<T> From<HashMap<Pos, T>> for SparseGrid<T> {
+    fn from(cells: HashMap<Pos, T>) -> Self {
+        Self { cells }
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        Self { cells: iter.into_iter().collect() }
+    }
+}
+
+/// A rectangular grid with a value at every position, with its
+/// corner at the origin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+    /// A `width` by `height` grid with `value` everywhere
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let mut grid = DenseGrid::new(3, 2, 0);
+    /// grid[Pos::new(2, 1)] = 7;
+    /// assert_eq!(grid.get(Pos::new(2, 1)), Some(&7));
+    /// assert_eq!(grid.get(Pos::new(3, 1)), None);
+    /// assert_eq!(grid.values().sum::<i32>(), 7);
+    /// ```
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self { width, height, cells: vec![value; width * height] }
+    }
+}
+
+impl<T> DenseGrid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(Pos::ORIGIN, Pos::new(self.width as isize - 1, self.height as isize - 1))
+    }
+
+    fn offset(&self, pos: Pos) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.offset(pos).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.offset(pos).map(|offset| &mut self.cells[offset])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.bounds().positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Update every value inside a rectangle of the grid
+    ///
+    /// ```
+    /// # use aoc2015::grid::*;
+    /// let mut grid = DenseGrid::new(4, 4, false);
+    /// grid.update(Bounds::new(Pos::new(1, 1), Pos::new(2, 3)), |lit| *lit = !*lit);
+    /// assert_eq!(grid.values().filter(|&&lit| lit).count(), 6);
+    /// ```
+    pub fn update(&mut self, region: Bounds, mut f: impl FnMut(&mut T)) {
+        let (Some(start), Some(end)) = (self.offset(region.min), self.offset(region.max)) else {
+            panic!("{}..{} is outside of the grid", region.min, region.max);
+        };
+
+        // Each row of the region is a contiguous run of cells
+        let width = region.width();
+        for row_start in (start..=end).step_by(self.width) {
+            self.cells[row_start..row_start + width].iter_mut().for_each(&mut f);
+        }
+    }
+}
+
+impl<T> Index<Pos> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for DenseGrid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
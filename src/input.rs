@@ -0,0 +1,296 @@
+//! Finding the puzzle input for each day
+//!
+//! Everybody gets their own input, so rather than only using the copies
+//! bundled into the crate, inputs are looked for in order
+//!
+//! 1. `$AOC_INPUT_DIR/dayN.txt`
+//! 2. `<config dir>/aoc2015/<profile>/dayN.txt`, where the config directory
+//!    is `$XDG_CONFIG_HOME` or `~/.config` (`%APPDATA%` on Windows), and the
+//!    profile is `$AOC_PROFILE` or `default`
+//! 3. The bundled copy, but only for the default profile, since it's
+//!    somebody else's input as far as any other profile is concerned
+//!
+//! However it was found, the input has its line endings turned into plain
+//! `\n` and any trailing newlines removed.
+
+use std::borrow::Cow;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Names a directory to look for inputs in before anywhere else
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Names the profile whose inputs should be used
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+/// The profile used when nobody asks for another one, which is also the
+/// profile the bundled inputs belong to
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The inputs bundled into the crate, by day
+static BUNDLED: [&str; 12] = [
+    include_str!("./input/day1.txt"),
+    include_str!("./input/day2.txt"),
+    include_str!("./input/day3.txt"),
+    include_str!("./input/day4.txt"),
+    include_str!("./input/day5.txt"),
+    include_str!("./input/day6.txt"),
+    include_str!("./input/day7.txt"),
+    include_str!("./input/day8.txt"),
+    include_str!("./input/day9.txt"),
+    include_str!("./input/day10.txt"),
+    include_str!("./input/day11.txt"),
+    include_str!("./input/day12.txt"),
+];
+
+/// Tidy up the line endings of some input text
+///
+/// ```
+/// # use aoc2015::input::normalize;
+/// assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+/// assert_eq!(normalize("  a\nb\n"), "  a\nb");
+/// ```
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let text = text.trim_end_matches(['\r', '\n']);
+    match text.contains('\r') {
+        true => Cow::Owned(text.replace("\r\n", "\n")),
+        false => Cow::Borrowed(text),
+    }
+}
+
+/// The input for a day that's bundled into the crate, if there is one.
+/// These are all stored with `\n` line endings, so only need their
+/// trailing newlines trimming
+///
+/// ```
+/// # use aoc2015::input::bundled;
+/// assert_eq!(bundled(4), Some("iwrupvqb"));
+/// assert!(bundled(0).is_none());
+/// ```
+pub fn bundled(day: u8) -> Option<&'static str> {
+    let index = usize::from(day).checked_sub(1)?;
+    BUNDLED.get(index).map(|text| text.trim_end_matches('\n'))
+}
+
+/// Where an input was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Bundled,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Bundled => write!(f, "bundled"),
+        }
+    }
+}
+
+/// An input that couldn't be loaded
+#[derive(Debug)]
+pub enum InputError {
+    /// There's no input for the day anywhere we looked
+    Missing { day: u8, profile: String },
+
+    /// There's an input file, but it couldn't be read
+    Unreadable { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { day, profile } => write!(f, "No input for day {day} in profile {profile}"),
+            InputError::Unreadable { path, error } => write!(f, "Couldn't read {}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Unreadable { error, .. } => Some(error),
+        }
+    }
+}
+
+/// The places to look for inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs {
+    profile: String,
+    input_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+}
+
+impl Inputs {
+    /// Look wherever the environment says to
+    pub fn from_env() -> Self {
+        let profile = env::var(PROFILE_VAR).unwrap_or_else(|_| DEFAULT_PROFILE.to_string());
+        Self {
+            profile,
+            input_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            config_dir: config_dir(),
+        }
+    }
+
+    /// Only use the inputs bundled into the crate
+    ///
+    /// ```
+    /// # use aoc2015::input::*;
+    /// let inputs = Inputs::bundled_only();
+    /// assert_eq!(inputs.locate(4), Some(Source::Bundled));
+    /// assert_eq!(inputs.load(4).unwrap(), "iwrupvqb");
+    /// assert!(inputs.load(26).is_err());
+    /// ```
+    pub fn bundled_only() -> Self {
+        Self { profile: DEFAULT_PROFILE.to_string(), input_dir: None, config_dir: None }
+    }
+
+    /// Use a different profile's inputs
+    ///
+    /// ```
+    /// # use aoc2015::input::*;
+    /// let inputs = Inputs::bundled_only().with_profile("somebody");
+    /// assert_eq!(inputs.profile(), "somebody");
+    /// assert_eq!(inputs.locate(4), None);
+    /// ```
+    pub fn with_profile(self, profile: &str) -> Self {
+        Self { profile: profile.to_string(), ..self }
+    }
+
+    /// Look in a particular directory before anywhere else
+    pub fn with_input_dir(self, dir: impl Into<PathBuf>) -> Self {
+        Self { input_dir: Some(dir.into()), ..self }
+    }
+
+    /// Stop looking in the input directory, and only use the profile's
+    /// own inputs
+    ///
+    /// ```
+    /// # use aoc2015::input::*;
+    /// let inputs = Inputs::bundled_only().with_input_dir("/tmp");
+    /// assert_eq!(inputs.without_input_dir(), Inputs::bundled_only());
+    /// ```
+    pub fn without_input_dir(self) -> Self {
+        Self { input_dir: None, ..self }
+    }
+
+    /// Look for profile directories somewhere other than the usual
+    /// config directory
+    pub fn with_config_dir(self, dir: impl Into<PathBuf>) -> Self {
+        Self { config_dir: Some(dir.into()), ..self }
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// The directory holding the current profile's inputs, if there's
+    /// anywhere to put it
+    pub fn profile_dir(&self) -> Option<PathBuf> {
+        self.config_dir.as_ref().map(|dir| dir.join("aoc2015").join(&self.profile))
+    }
+
+    fn file_name(day: u8) -> String {
+        format!("day{day}.txt")
+    }
+
+    /// Figure out where the input for a day would come from
+    pub fn locate(&self, day: u8) -> Option<Source> {
+        let files = [self.input_dir.clone(), self.profile_dir()]
+            .into_iter()
+            .flatten()
+            .map(|dir| dir.join(Self::file_name(day)));
+
+        for file in files {
+            if file.is_file() {
+                return Some(Source::File(file));
+            }
+        }
+
+        (self.profile == DEFAULT_PROFILE && bundled(day).is_some()).then_some(Source::Bundled)
+    }
+
+    /// Load the input for a day
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        match self.locate(day) {
+            Some(Source::File(path)) => read(&path),
+            Some(Source::Bundled) => Ok(bundled(day).expect("Was just located").to_string()),
+            None => Err(InputError::Missing { day, profile: self.profile.clone() }),
+        }
+    }
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path)
+        .map(|text| normalize(&text).into_owned())
+        .map_err(|error| InputError::Unreadable { path: path.to_path_buf(), error })
+}
+
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::var_os("APPDATA").map(PathBuf::from);
+    }
+
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Load the input for a day from wherever the environment says to look
+pub fn load(day: u8) -> Result<String, InputError> {
+    Inputs::from_env().load(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn bundled_inputs_are_normal() {
+        for day in 1..=BUNDLED.len() as u8 {
+            assert!(!BUNDLED[usize::from(day) - 1].contains('\r'), "Day {day}");
+            assert_eq!(bundled(day), Some(normalize(BUNDLED[usize::from(day) - 1]).as_ref()));
+        }
+    }
+
+    #[test]
+    pub fn input_dir_comes_first() {
+        let dir = env::temp_dir().join(format!("aoc2015-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day4.txt"), "abcdef\r\n\r\n").unwrap();
+
+        let inputs = Inputs::bundled_only().with_input_dir(&dir);
+        assert_eq!(inputs.locate(4), Some(Source::File(dir.join("day4.txt"))));
+        assert_eq!(inputs.load(4).unwrap(), "abcdef");
+        assert_eq!(inputs.locate(1), Some(Source::Bundled));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn profiles_have_their_own_directories() {
+        let dir = env::temp_dir().join(format!("aoc2015-config-{}", std::process::id()));
+        let inputs = Inputs { config_dir: Some(dir.clone()), ..Inputs::bundled_only() }.with_profile("elf");
+
+        fs::create_dir_all(inputs.profile_dir().unwrap()).unwrap();
+        fs::write(inputs.profile_dir().unwrap().join("day1.txt"), "(()\n").unwrap();
+
+        assert_eq!(inputs.profile_dir(), Some(dir.join("aoc2015").join("elf")));
+        assert_eq!(inputs.load(1).unwrap(), "(()");
+        assert!(matches!(inputs.load(2), Err(InputError::Missing { day: 2, .. })));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,15 +1,56 @@
 //! Advent of Code 2015
+//!
+//! Builds on stable Rust, back to the `rust-version` in `Cargo.toml`.
+//! Fast paths that need a nightly compiler are behind the `nightly`
+//! feature, and always have a stable fallback.
+//!
+//! Days 1, 2, 5 and 7 don't need the standard library, only `alloc`, so
+//! they can be used with `default-features = false` in `no_std` builds.
+//! Everything else needs the `std` feature, which is on by default.
 
-#![feature(iter_array_chunks)]
-#![feature(iter_collect_into)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(iter_collect_into))]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod bench;
 pub mod day1;
+#[cfg(feature = "std")]
+pub mod day10;
+#[cfg(feature = "std")]
+pub mod day11;
+#[cfg(feature = "std")]
+pub mod day12;
 pub mod day2;
+#[cfg(feature = "std")]
 pub mod day3;
+#[cfg(feature = "std")]
 pub mod day4;
 pub mod day5;
+#[cfg(feature = "std")]
 pub mod day6;
 pub mod day7;
+#[cfg(feature = "std")]
+pub mod day8;
+#[cfg(feature = "std")]
+pub mod day9;
+#[cfg(feature = "std")]
+pub mod gen;
+#[cfg(feature = "std")]
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod manifest;
+pub mod predicates;
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod solutions;
+#[cfg(feature = "std")]
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub mod prelude {
     use nom::IResult;
@@ -19,9 +60,9 @@ pub mod prelude {
 
     /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and 
     /// trailing whitespace, returning the output of `inner`.
-    pub fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+    pub fn ws<'a, F, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
     where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
+    F: 'a + Fn(&'a str) -> IResult<&'a str, O, E>,
     {
         delimited(
             multispace0,
@@ -0,0 +1,261 @@
+//! Run the solutions from the command line
+//!
+//! ```text
+//! aoc2015 run [--format text|json] [DAY...]
+//! aoc2015 verify [--manifest PATH] [--profile NAME]... [--all]
+//! aoc2015 gen DAY [--seed N]
+//! aoc2015 bench [--time MS] [--save PATH] [--baseline PATH] [--threshold PERCENT] [DAY...]
+//! ```
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc2015::bench::{self, Baseline, Settings, HEADER};
+use aoc2015::gen::{self, Netlist};
+use aoc2015::input::Inputs;
+use aoc2015::manifest::{Manifest, MANIFEST_FILE};
+use aoc2015::rng::Rng;
+use aoc2015::solutions::{self, Day, Run, DAYS};
+use aoc2015::verify::{verify, verify_named, Report};
+
+const USAGE: &str = "\
+Usage:
+    aoc2015 run [--format text|json] [DAY...]
+        Print the answers for the given days, or every day. As JSON, each
+        answer is an object with its day, part, answer, elapsed_ns and the
+        MD5 input_hash of the input it came from
+
+    aoc2015 verify [--manifest PATH] [--profile NAME]... [--all]
+        Check the answers against a manifest (answers.toml by default) for
+        the current profile, the named profiles, or every profile in it.
+        Named profiles only use the inputs in their own profile directory,
+        never $AOC_INPUT_DIR
+
+    aoc2015 gen DAY [--seed N]
+        Make up an input for a day, about the size of a real one
+
+    aoc2015 bench [--time MS] [--save PATH] [--baseline PATH] [--threshold PERCENT] [DAY...]
+        Time parsing and both parts of the given days, or every day, spending
+        about MS milliseconds (1000 by default) on each. The timings are
+        printed as tab separated values, and can be saved to use as a
+        baseline later. Anything more than PERCENT (10 by default) slower
+        than the baseline counts as a regression";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.split_first() {
+        Some((command, rest)) if command == "run" => run(rest),
+        Some((command, rest)) if command == "verify" => verify_profiles(rest),
+        Some((command, rest)) if command == "gen" => generate(rest),
+        Some((command, rest)) if command == "bench" => run_benchmarks(rest),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn days(args: &[String]) -> Result<Vec<Day>, String> {
+    if args.is_empty() {
+        return Ok(DAYS.to_vec());
+    }
+
+    args.iter()
+        .map(|arg| {
+            arg.parse()
+                .ok()
+                .and_then(solutions::day)
+                .copied()
+                .ok_or_else(|| format!("There's no solution for day {arg}"))
+        })
+        .collect()
+}
+
+fn run(mut args: &[String]) -> Result<ExitCode, String> {
+    let mut json = false;
+    let mut day_args = Vec::new();
+
+    while let Some((arg, rest)) = args.split_first() {
+        args = rest;
+        match arg.as_str() {
+            "--format" => {
+                let (format, rest) = args.split_first().ok_or_else(|| format!("{arg} needs a value"))?;
+                args = rest;
+                json = match format.as_str() {
+                    "text" => false,
+                    "json" => true,
+                    _ => return Err(format!("Don't know the {format} format, only text or json")),
+                };
+            }
+            _ => day_args.push(arg.clone()),
+        }
+    }
+
+    let inputs = Inputs::from_env();
+    let mut runs = Vec::new();
+    for day in days(&day_args)? {
+        let input = inputs.load(day.day).map_err(|err| err.to_string())?;
+        for part in [1, 2] {
+            let run = Run::new(&day, part, &input).map_err(|err| format!("Day {} part {part}: {err}", day.day))?;
+            if !json {
+                println!("{run}");
+            }
+            runs.push(run);
+        }
+    }
+
+    if json {
+        let objects = runs.iter().map(|run| format!("  {}", run.to_json())).collect::<Vec<_>>();
+        println!("[\n{}\n]", objects.join(",\n"));
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn verify_profiles(mut args: &[String]) -> Result<ExitCode, String> {
+    let mut manifest_path = None;
+    let mut profiles = Vec::new();
+    let mut all = false;
+
+    while let Some((arg, rest)) = args.split_first() {
+        args = rest;
+        match arg.as_str() {
+            "--all" => all = true,
+            "--manifest" | "--profile" => {
+                let (value, rest) = args.split_first().ok_or_else(|| format!("{arg} needs a value"))?;
+                args = rest;
+                match arg.as_str() {
+                    "--manifest" => manifest_path = Some(value.clone()),
+                    _ => profiles.push(value.clone()),
+                }
+            }
+            _ => return Err(format!("Don't know what to do with {arg}\n\n{USAGE}")),
+        }
+    }
+
+    let path = manifest_path.unwrap_or_else(|| MANIFEST_FILE.to_string());
+    let text = fs::read_to_string(&path).map_err(|err| format!("Couldn't read {path}: {err}"))?;
+    let manifest = Manifest::parse(&text).map_err(|err| format!("{path}: {err}"))?;
+
+    // Only the current profile's inputs can come from the input directory.
+    // Any profile asked for by name has to use its own
+    let inputs = Inputs::from_env();
+    if all {
+        profiles.extend(manifest.profiles().map(String::from));
+    }
+    let report = match profiles.is_empty() {
+        true => Report(verify(&inputs, &manifest, DAYS)),
+        false => verify_named(&inputs, &profiles.iter().map(String::as_str).collect::<Vec<_>>(), &manifest, DAYS),
+    };
+
+    println!("{report}");
+    Ok(if report.passed() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn generate(args: &[String]) -> Result<ExitCode, String> {
+    let (day, seed) = match args {
+        [day] => (day, 2015),
+        [day, flag, seed] if flag == "--seed" => (day, seed.parse().map_err(|_| format!("{seed} isn't a valid seed"))?),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let mut rng = Rng::new(seed);
+    let input = match day.as_str() {
+        "1" => gen::day1(&mut rng, 7000),
+        "2" => gen::day2(&mut rng, 1000, 30),
+        "3" => gen::day3(&mut rng, 8192),
+        "4" => gen::day4(&mut rng, 8),
+        "5" => gen::day5(&mut rng, 1000, 16),
+        "6" => gen::day6(&mut rng, 300, 1000),
+        "7" => gen::day7(&mut rng, &Netlist::default()),
+        "8" => gen::day8(&mut rng, 300),
+        "9" => gen::day9(&mut rng, 8),
+        "10" => gen::day10(&mut rng, 10),
+        "11" => gen::day11(&mut rng, 8),
+        "12" => gen::day12(&mut rng, 5),
+        _ => return Err(format!("Can't make up an input for day {day}")),
+    };
+
+    println!("{input}");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_benchmarks(mut args: &[String]) -> Result<ExitCode, String> {
+    let mut settings = Settings::default();
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut day_args = Vec::new();
+
+    while let Some((arg, rest)) = args.split_first() {
+        args = rest;
+        if !arg.starts_with("--") {
+            day_args.push(arg.clone());
+            continue;
+        }
+
+        let (value, rest) = args.split_first().ok_or_else(|| format!("{arg} needs a value"))?;
+        args = rest;
+        let bad_value = || format!("{value} isn't a valid value for {arg}");
+        match arg.as_str() {
+            "--time" => settings.target = Duration::from_millis(value.parse().map_err(|_| bad_value())?),
+            "--save" => save = Some(value.clone()),
+            "--baseline" => baseline = Some(value.clone()),
+            "--threshold" => threshold = value.parse().map_err(|_| bad_value())?,
+            _ => return Err(format!("Don't know what to do with {arg}\n\n{USAGE}")),
+        }
+    }
+
+    let baseline = baseline
+        .map(|path| {
+            let text = fs::read_to_string(&path).map_err(|err| format!("Couldn't read {path}: {err}"))?;
+            Baseline::parse(&text).map_err(|err| format!("{path}: {err}"))
+        })
+        .transpose()?;
+
+    let inputs = Inputs::from_env();
+    let mut measurements = Vec::new();
+    println!("{HEADER}");
+    for day in days(&day_args)? {
+        let input = inputs.load(day.day).map_err(|err| err.to_string())?;
+        for measurement in bench::bench(&day, &input, &settings).map_err(|err| err.to_string())? {
+            println!("{measurement}");
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = save {
+        let lines = measurements.iter().map(ToString::to_string).collect::<Vec<_>>();
+        fs::write(&path, format!("{HEADER}\n{}\n", lines.join("\n"))).map_err(|err| format!("Couldn't write {path}: {err}"))?;
+    }
+
+    // The comparison goes to stderr, to keep stdout machine readable
+    let Some(baseline) = baseline else {
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let mut regressed = false;
+    for comparison in bench::compare(&measurements, &baseline) {
+        let regression = comparison.is_regression(threshold / 100.0);
+        regressed |= regression;
+        let time = |time: Option<Duration>| time.map_or("-".to_string(), |time| format!("{time:.2?}"));
+        eprintln!(
+            "day {:>2} {:<5}  {:>10} -> {:>10}  {:>8}{}",
+            comparison.day,
+            comparison.stage,
+            time(comparison.baseline),
+            time(comparison.current),
+            comparison.change.map_or("N/A".to_string(), |change| format!("{:+.1}%", change * 100.0)),
+            if regression { "  REGRESSION" } else { "" },
+        );
+    }
+
+    Ok(if regressed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
@@ -0,0 +1,177 @@
+//! The expected answers for everybody's inputs
+//!
+//! Answers live in a small subset of TOML, with a table per profile and a
+//! dotted key per part
+//!
+//! ```toml
+//! # My answers
+//! [default]
+//! day1.part1 = 74
+//! day1.part2 = 1795
+//!
+//! [elf]
+//! day1.part1 = -3
+//! day11.part1 = "hxbxxyzz"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use nom::IResult;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, digit1, none_of, space0, u8 as number};
+use nom::combinator::{all_consuming, eof, map, map_res, opt, recognize, rest};
+use nom::multi::many0;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+
+use crate::solutions::Answer;
+
+/// The name of the manifest file
+pub const MANIFEST_FILE: &str = "answers.toml";
+
+/// Expected answers, by profile, day and part
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    profiles: BTreeMap<String, BTreeMap<(u8, u8), Answer>>,
+}
+
+/// A line of a manifest that couldn't be understood
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    /// The line the problem is on, counting from one
+    pub line: usize,
+
+    /// The text that couldn't be parsed
+    pub text: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.text.is_empty() {
+            true => write!(f, "Answer on line {} doesn't belong to a profile", self.line),
+            false => write!(f, "Couldn't understand line {} of the answers: {}", self.line, self.text),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+enum Line {
+    Profile(String),
+    Answer(u8, u8, Answer),
+    Blank,
+}
+
+impl Manifest {
+    /// Load a manifest from its text
+    ///
+    /// ```
+    /// # use aoc2015::manifest::*;
+    /// # use aoc2015::solutions::Answer;
+    /// let manifest = Manifest::parse(r#"
+    ///     [default]
+    ///     day1.part1 = 74  # the floor
+    ///     day1.part2 = 1795
+    ///
+    ///     [elf]
+    ///     day1.part1 = -3
+    ///     day11.part1 = "hxbxxyzz"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(manifest.profiles().collect::<Vec<_>>(), ["default", "elf"]);
+    /// assert_eq!(manifest.expected("default", 1, 2), Some(&Answer::from(1795)));
+    /// assert_eq!(manifest.expected("elf", 1, 1), Some(&Answer::from(-3)));
+    /// assert_eq!(manifest.expected("elf", 11, 1), Some(&Answer::from("hxbxxyzz")));
+    /// assert_eq!(manifest.expected("elf", 1, 2), None);
+    ///
+    /// assert_eq!(Manifest::parse("[a]\nday1.part1 = 3\nday1.part = 4").unwrap_err().line, 3);
+    /// assert_eq!(Manifest::parse("day1.part1 = 3").unwrap_err().line, 1);
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        let mut manifest = Manifest::default();
+        let mut current = None;
+
+        for (index, text) in text.lines().enumerate() {
+            let line = index + 1;
+            let text = text.trim();
+            let (_, parsed) = all_consuming(manifest_line)(text)
+                .map_err(|_| ManifestError { line, text: text.to_string() })?;
+
+            match parsed {
+                Line::Profile(name) => {
+                    manifest.profiles.entry(name.clone()).or_default();
+                    current = Some(name);
+                }
+                Line::Answer(day, part, answer) => {
+                    let profile = current.as_ref().ok_or(ManifestError { line, text: String::new() })?;
+                    manifest.insert(profile, day, part, answer);
+                }
+                Line::Blank => {}
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Record the answer expected for a profile
+    pub fn insert(&mut self, profile: &str, day: u8, part: u8, answer: Answer) {
+        self.profiles.entry(profile.to_string()).or_default().insert((day, part), answer);
+    }
+
+    /// The names of all the profiles, in order
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// The answer expected for a part of a day, if anybody's written it down
+    pub fn expected(&self, profile: &str, day: u8, part: u8) -> Option<&Answer> {
+        self.profiles.get(profile)?.get(&(day, part))
+    }
+}
+
+fn manifest_line(input: &str) -> IResult<&str, Line> {
+    terminated(
+        alt((
+            map(profile, |name| Line::Profile(name.to_string())),
+            map(answer, |(day, part, answer)| Line::Answer(day, part, answer)),
+            map(eof, |_| Line::Blank),
+            map(comment, |_| Line::Blank),
+        )),
+        pair(space0, opt(comment)),
+    )(input)
+}
+
+fn comment(input: &str) -> IResult<&str, &str> {
+    preceded(char('#'), rest)(input)
+}
+
+fn profile(input: &str) -> IResult<&str, &str> {
+    delimited(
+        pair(char('['), space0),
+        take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-'),
+        pair(space0, char(']')),
+    )(input)
+}
+
+fn answer(input: &str) -> IResult<&str, (u8, u8, Answer)> {
+    map(
+        separated_pair(
+            pair(preceded(tag("day"), number), preceded(tag(".part"), number)),
+            delimited(space0, char('='), space0),
+            value,
+        ),
+        |((day, part), answer)| (day, part, answer),
+    )(input)
+}
+
+fn value(input: &str) -> IResult<&str, Answer> {
+    alt((
+        map_res(digit1, |digits: &str| digits.parse::<u64>().map(Answer::Unsigned)),
+        map_res(recognize(tuple((char('-'), digit1))), |digits: &str| digits.parse::<i64>().map(Answer::Signed)),
+        map(
+            delimited(char('"'), recognize(many0(none_of("\""))), char('"')),
+            Answer::from,
+        ),
+    ))(input)
+}
@@ -0,0 +1,71 @@
+//! Checks on strings of letters that more than one day's rules are made
+//! of
+//!
+//! They all work on bytes, so they don't care whether the letters came
+//! from a `&str` or a password being counted up in place.
+
+/// The first position of any of `letters`
+///
+/// ```
+/// # use aoc2015::predicates::*;
+/// assert_eq!(first_of(b"abcdoe", b"oi"), Some(4));
+/// assert_eq!(first_of(b"abcde", b"oi"), None);
+/// ```
+pub fn first_of(s: &[u8], letters: &[u8]) -> Option<usize> {
+    s.iter().position(|letter| letters.contains(letter))
+}
+
+/// Whether the same letter turns up twice in a row anywhere
+///
+/// ```
+/// # use aoc2015::predicates::*;
+/// assert!(has_doubled_letter(b"abbc"));
+/// assert!(!has_doubled_letter(b"abab"));
+/// ```
+pub fn has_doubled_letter(s: &[u8]) -> bool {
+    s.windows(2).any(|w| w[0] == w[1])
+}
+
+/// How many different letters turn up twice in a row. Pairs of different
+/// letters can never overlap, so this is also how many non-overlapping
+/// pairs there are of different letters
+///
+/// ```
+/// # use aoc2015::predicates::*;
+/// assert_eq!(distinct_pairs(b"aabcc"), 2);
+/// assert_eq!(distinct_pairs(b"aaa"), 1);
+/// assert_eq!(distinct_pairs(b"aabaa"), 1);
+/// assert_eq!(distinct_pairs(b"abc"), 0);
+/// ```
+pub fn distinct_pairs(s: &[u8]) -> usize {
+    let mut seen = [false; 256];
+    for w in s.windows(2).filter(|w| w[0] == w[1]) {
+        seen[usize::from(w[0])] = true;
+    }
+
+    seen.iter().filter(|&&seen| seen).count()
+}
+
+/// Whether any of the two letter `pairs` turn up
+///
+/// ```
+/// # use aoc2015::predicates::*;
+/// assert!(contains_any_pair(b"xcdx", &[*b"ab", *b"cd"]));
+/// assert!(!contains_any_pair(b"acbd", &[*b"ab", *b"cd"]));
+/// ```
+pub fn contains_any_pair(s: &[u8], pairs: &[[u8; 2]]) -> bool {
+    s.windows(2).any(|w| pairs.iter().any(|pair| w == pair))
+}
+
+/// Whether there's a run of `length` letters going up one at a time,
+/// like `abc`
+///
+/// ```
+/// # use aoc2015::predicates::*;
+/// assert!(has_straight(b"hijklmmn", 3));
+/// assert!(!has_straight(b"abd", 3));
+/// assert!(has_straight(b"x", 1));
+/// ```
+pub fn has_straight(s: &[u8], length: usize) -> bool {
+    length == 0 || s.windows(length).any(|w| w.windows(2).all(|pair| pair[0].checked_add(1) == Some(pair[1])))
+}
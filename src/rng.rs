@@ -0,0 +1,112 @@
+//! A small seeded random number generator, for anything that needs to
+//! make up reproducible data
+
+/// SplitMix64, which is tiny, fast and plenty random enough for
+/// making up puzzle data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+    }
+
+    /// A uniformly random number in `0..bound`
+    ///
+    /// ```
+    /// # use aoc2015::rng::Rng;
+    /// let mut rng = Rng::new(42);
+    /// assert!((0..1000).all(|_| rng.below(10) < 10));
+    /// ```
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.below_u128(u128::from(bound)) as u64
+    }
+
+    /// A uniformly random number in `0..bound`, for really big bounds
+    pub fn below_u128(&mut self, bound: u128) -> u128 {
+        if bound == 0 {
+            panic!("Can't pick a number below zero");
+        }
+
+        // Throw away anything from the last partial run of `bound` numbers,
+        // otherwise the low numbers would come up slightly more often
+        let zone = u128::MAX - (u128::MAX % bound);
+        loop {
+            let candidate = self.next_u128();
+            if candidate < zone {
+                return candidate % bound;
+            }
+        }
+    }
+
+    /// A uniformly random number in an inclusive range
+    ///
+    /// ```
+    /// # use aoc2015::rng::Rng;
+    /// let mut rng = Rng::new(7);
+    /// assert!((0..1000).all(|_| (3..=5).contains(&rng.between(3, 5))));
+    /// assert_eq!(rng.between(4, 4), 4);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `high` is less than `low`, since there's nothing in between
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        let Some(span) = high.checked_sub(low) else {
+            panic!("Can't pick a number between {low} and {high}, the range is empty");
+        };
+
+        low + self.below_u128(u128::from(span) + 1) as u64
+    }
+
+    /// Something picked uniformly at random from a non-empty slice
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Shuffle a slice in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ranges_at_the_top() {
+        let mut rng = Rng::new(1);
+        assert!((0..100).all(|_| rng.between(u64::MAX - 1, u64::MAX) >= u64::MAX - 1));
+        rng.between(0, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't pick a number between 1 and 0, the range is empty")]
+    pub fn inverted_range() {
+        Rng::new(1).between(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't pick a number between 1 and 0, the range is empty")]
+    pub fn presents_need_a_side() {
+        crate::gen::day2(&mut Rng::new(1), 1, 0);
+    }
+}
@@ -0,0 +1,441 @@
+//! Every day's solutions, registered in one place so they can be run and
+//! checked without knowing how each day goes about it
+
+use std::fmt;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12};
+
+/// The answer to one part of a day's puzzle
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl PartialEq for Answer {
+    /// Numbers are the same answer whichever way they're stored
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Unsigned(b)) | (Answer::Unsigned(b), Answer::Signed(a)) => {
+                u64::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Answer {
+    /// The answer as a JSON value, keeping numbers as numbers
+    ///
+    /// ```
+    /// # use aoc2015::solutions::Answer;
+    /// assert_eq!(Answer::from(-3).to_json(), "-3");
+    /// assert_eq!(Answer::from(2797u16).to_json(), "2797");
+    /// assert_eq!(Answer::from("say \"hi\"\n").to_json(), r#""say \"hi\"\n""#);
+    /// ```
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Text(s) => json_string(s),
+            number => number.to_string(),
+        }
+    }
+}
+
+/// Quote and escape a string for JSON
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", u32::from(c)).expect("Writing to a string can't fail"),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $wide:ty: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Self {
+                    Answer::$variant(n as $wide)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Signed as i64: i16, i32, i64);
+answer_from!(Unsigned as u64: u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Why a puzzle couldn't be solved, usually because the input didn't
+/// make sense
+pub type SolveError = Box<dyn std::error::Error>;
+
+/// A part that a day doesn't have. Every day only has parts 1 and 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoSuchPart {
+    pub day: u8,
+    pub part: u8,
+}
+
+impl fmt::Display for NoSuchPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} has no part {}", self.day, self.part)
+    }
+}
+
+impl std::error::Error for NoSuchPart {}
+
+/// Solves one part of a puzzle, given the (normalised) input
+pub type Solver = fn(&str) -> Result<Answer, SolveError>;
+
+/// Reads the input into whatever a day works on, without solving
+/// anything, and says how many things it found
+pub type Parser = fn(&str) -> Result<usize, SolveError>;
+
+/// Both parts of a day's puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+
+    /// Days that work straight on the text don't have a parser
+    pub parse: Option<Parser>,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Day {
+    pub fn parts(&self) -> [(u8, Solver); 2] {
+        [(1, self.part1), (2, self.part2)]
+    }
+
+    pub fn part(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+/// The answer to one part of a day, and how it was got
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+
+    /// The MD5 digest of the input, in hex, so answers can be matched
+    /// up with the input they came from
+    pub input_hash: String,
+}
+
+impl Run {
+    /// Solve one part of a day, timing it
+    ///
+    /// ```
+    /// # use aoc2015::solutions::*;
+    /// let run = Run::new(day(1).unwrap(), 1, "(()").unwrap();
+    /// assert_eq!(run.answer, Answer::from(1));
+    /// assert_eq!(run.input_hash, "41b14b78b40b0d404aa742461c7160a9");
+    ///
+    /// assert!(Run::new(day(1).unwrap(), 1, "(x)").is_err());
+    ///
+    /// let err = Run::new(day(1).unwrap(), 3, "(()").unwrap_err();
+    /// assert_eq!(err.downcast_ref(), Some(&NoSuchPart { day: 1, part: 3 }));
+    /// assert_eq!(err.to_string(), "Day 1 has no part 3");
+    /// ```
+    pub fn new(day: &Day, part: u8, input: &str) -> Result<Self, SolveError> {
+        let solver = day.part(part).ok_or(NoSuchPart { day: day.day, part })?;
+        let start = Instant::now();
+        let answer = solver(input)?;
+        let elapsed = start.elapsed();
+
+        Ok(Self { day: day.day, part, answer, elapsed, input_hash: format!("{:x}", md5::compute(input)) })
+    }
+
+    /// A JSON object with the day, part, answer, time taken in
+    /// nanoseconds and input hash
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use aoc2015::solutions::*;
+    /// let run = Run { day: 7, part: 1, answer: 16076u16.into(), elapsed: Duration::from_micros(5), input_hash: "abc".into() };
+    /// assert_eq!(run.to_json(), r#"{"day":7,"part":1,"answer":16076,"elapsed_ns":5000,"input_hash":"abc"}"#);
+    /// ```
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"day":{},"part":{},"answer":{},"elapsed_ns":{},"input_hash":{}}}"#,
+            self.day,
+            self.part,
+            self.answer.to_json(),
+            self.elapsed.as_nanos(),
+            json_string(&self.input_hash),
+        )
+    }
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} part {}: {} ({:.2?})", self.day, self.part, self.answer, self.elapsed)
+    }
+}
+
+/// Every day with a solution, in order
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        parse: None,
+        part1: |input| Ok(day1::find_floor(input)?.into()),
+        part2: |input| Ok(day1::find_basement(input)?.into()),
+    },
+    Day {
+        day: 2,
+        parse: Some(|input| Ok(presents(input)?.len())),
+        part1: |input| total(input, |x| x.total_area() + x.slack()),
+        part2: |input| total(input, |x| x.ribbon_length() + x.bow_length()),
+    },
+    Day {
+        day: 3,
+        parse: Some(|input| Ok(day3::Route::follow(input.trim().chars())?.distance())),
+        part1: |input| Ok(day3::collect_visits(input.trim().chars())?.len().into()),
+        part2: |input| Ok(day3::deliver(2, day3::Turns::RoundRobin, input.trim().chars())?.houses().into()),
+    },
+    Day {
+        day: 4,
+        parse: None,
+        part1: |input| Ok(day4::mine_advent_coin_v1(input.trim()).into()),
+        part2: |input| Ok(day4::mine_advent_coin_v2(input.trim()).into()),
+    },
+    Day {
+        day: 5,
+        parse: None,
+        part1: |input| Ok(input.lines().filter(|line| day5::is_nice_v1(line)).count().into()),
+        part2: |input| Ok(input.lines().filter(|line| day5::is_nice_v2(line)).count().into()),
+    },
+    Day {
+        day: 6,
+        parse: Some(|input| Ok(instructions(input)?.len())),
+        part1: |input| Ok(day6::lights_lit(&instructions(input)?).into()),
+        part2: |input| Ok(day6::total_brightness(&instructions(input)?).into()),
+    },
+    Day {
+        day: 7,
+        parse: Some(|input| Ok(day7::Circuit::load(&wires(input))?.len())),
+        part1: |input| {
+            let lines = wires(input);
+            let mut circuit = circuit(&lines)?;
+            Ok(circuit.evaluate("a").into())
+        },
+        part2: |input| {
+            let lines = wires(input);
+            let mut circuit = circuit(&lines)?;
+            let a = circuit.evaluate("a");
+            circuit.reset();
+            circuit.solve("b", a);
+            Ok(circuit.evaluate("a").into())
+        },
+    },
+    Day {
+        day: 8,
+        parse: Some(|input| Ok(literals(input)?.len())),
+        part1: |input| Ok(day8::code_overhead(&literals(input)?).into()),
+        part2: |input| Ok(day8::encoding_overhead(&literals(input)?).into()),
+    },
+    Day {
+        day: 9,
+        parse: Some(|input| Ok(day9::Graph::parse(input)?.len())),
+        part1: |input| route(input, day9::Goal::Shortest),
+        part2: |input| route(input, day9::Goal::Longest),
+    },
+    Day {
+        day: 10,
+        parse: Some(|input| Ok(day10::parse_seed(input)?.len())),
+        part1: |input| length_after(input, 40),
+        part2: |input| length_after(input, 50),
+    },
+    Day {
+        day: 11,
+        parse: Some(|input| Ok(day11::parse_password(input)?.len())),
+        part1: |input| Ok(day11::next_password(input, &day11::SANTA)?.into()),
+        part2: |input| {
+            let expired = day11::next_password(input, &day11::SANTA)?;
+            Ok(day11::next_password(&expired, &day11::SANTA)?.into())
+        },
+    },
+    Day {
+        day: 12,
+        parse: Some(|input| Ok(day12::Value::parse(input)?.children().count())),
+        part1: |input| Ok(sum(&day12::Value::parse(input)?, &day12::Everything)),
+        part2: |input| {
+            let red = day12::ObjectsContaining(day12::Value::String("red".to_string()));
+            Ok(sum(&day12::Value::parse(input)?, &red))
+        },
+    },
+];
+
+fn presents(input: &str) -> Result<Vec<day2::Present>, day2::PresentError> {
+    input.lines().map(str::parse).collect()
+}
+
+/// Add something up over every present, as long as it fits in an answer
+fn total(input: &str, each: fn(&day2::Present) -> u32) -> Result<Answer, SolveError> {
+    let total = presents(input)?.iter().try_fold(0u64, |total, present| total.checked_add(u64::from(each(present))));
+    Ok(total.ok_or("The total is too big for an answer")?.into())
+}
+
+fn wires(input: &str) -> Vec<&str> {
+    input.lines().map(str::trim).collect()
+}
+
+/// A circuit with a wire `a` to read the answer from
+fn circuit<'a>(lines: &[&'a str]) -> Result<day7::Circuit<'a>, SolveError> {
+    let circuit = day7::Circuit::load(lines)?;
+    match circuit.drives("a") {
+        true => Ok(circuit),
+        false => Err(day7::CircuitError::Undriven("a".to_string()).into()),
+    }
+}
+
+fn instructions(input: &str) -> Result<Vec<day6::Instruction>, day6::InstructionError> {
+    input.lines().map(day6::Instruction::parse).collect()
+}
+
+fn literals(input: &str) -> Result<Vec<day8::Literal<'_>>, day8::LiteralError> {
+    input.lines().map(day8::Literal::parse).collect()
+}
+
+/// The distance of the best route through every city. There are too
+/// many routes to be sure of the best one through more cities than
+/// [`day9::HELD_KARP_LIMIT`], so those just get a good one, marked as such
+fn route(input: &str, goal: day9::Goal) -> Result<Answer, SolveError> {
+    let graph = day9::Graph::parse(input)?;
+    if graph.len() > day9::HELD_KARP_LIMIT {
+        let route = day9::approximate(&graph, goal).ok_or("Couldn't find a route through every city")?;
+        return Ok(format!("{} (not exact)", route.distance).into());
+    }
+
+    let route = day9::held_karp(&graph, goal).ok_or("There's no route through every city")?;
+    Ok(route.distance.into())
+}
+
+/// How long the seed gets after some rounds of look-and-say
+fn length_after(input: &str, rounds: usize) -> Result<Answer, SolveError> {
+    let length = day10::length_after(day10::parse_seed(input)?, rounds).ok_or("The string gets too long to count")?;
+    Ok(length.into())
+}
+
+/// The sum of the numbers in a document, which is a whole number unless
+/// the document has fractions in it
+fn sum(document: &day12::Value, filter: &impl day12::Filter) -> Answer {
+    let sum = day12::sum_numbers(document, filter);
+    match sum.fract() == 0.0 && sum.abs() < 1e15 {
+        true => Answer::from(sum as i64),
+        false => Answer::from(sum.to_string()),
+    }
+}
+
+/// Look up the solutions for a day
+///
+/// ```
+/// # use aoc2015::solutions::*;
+/// let day = day(1).unwrap();
+/// assert_eq!((day.part1)("(()").unwrap(), Answer::Signed(1));
+/// assert_eq!((day.part2)("())").unwrap(), Answer::Unsigned(3));
+/// assert!(aoc2015::solutions::day(26).is_none());
+/// ```
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+
+    #[test]
+    pub fn numbers_compare_by_value() {
+        assert_eq!(Answer::from(74), Answer::from(74usize));
+        assert_ne!(Answer::from(-1), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(1), Answer::from("1"));
+    }
+
+    #[test]
+    pub fn huge_presents_are_errors() {
+        let day = day(2).unwrap();
+        for solver in [day.part1, day.part2] {
+            assert_eq!(solver("2x3x4\n4000000000x4000000000x1").unwrap_err().to_string(), "The present is too big to measure");
+        }
+
+        // The biggest present that can be measured
+        let present = "1x1x1073741823";
+        assert_eq!((day.part1)(present).unwrap(), Answer::from(u32::MAX));
+        assert_eq!((day.part2)(&[present; 4].join("\n")).unwrap(), Answer::from(4 * 1073741827u64));
+        assert!((day.part1)("1x1x1073741824").is_err());
+    }
+
+    #[test]
+    pub fn long_routes_and_many_cities() {
+        let day = day(9).unwrap();
+        let input = "A to B = 4000000000\nB to C = 4000000000";
+        assert_eq!((day.part1)(input).unwrap(), Answer::from(8000000000u64));
+
+        let input = "A to B = 1\nC to D = 1";
+        assert_eq!((day.part1)(input).unwrap_err().to_string(), "There's no route through every city");
+
+        // 22 cities in a line, which is too many to be sure of the answer
+        let cities = ('a'..='v').collect::<Vec<_>>();
+        let input = cities.windows(2).map(|pair| format!("C{} to C{} = 2", pair[0], pair[1])).collect::<Vec<_>>().join("\n");
+        assert_eq!((day.part1)(&input).unwrap(), Answer::from("42 (not exact)"));
+
+        let input = ('a'..='u').map(|city| format!("A to C{city} = 1")).collect::<Vec<_>>().join("\n");
+        assert_eq!((day.part1)(&input).unwrap_err().to_string(), "Couldn't find a route through every city");
+    }
+
+    #[test]
+    pub fn bad_moves_are_errors() {
+        let day = day(3).unwrap();
+        for solver in [day.part1, day.part2] {
+            assert_eq!(solver("^>v<x").unwrap_err().to_string(), "Don't know what to do with a x");
+        }
+        assert!((day.parse.unwrap())("^>v<x").is_err());
+        assert!((day.part1)("^>v<n").is_err());
+        assert_eq!((day.part1)("^>v<\n").unwrap(), Answer::from(4usize));
+    }
+}
@@ -0,0 +1,237 @@
+//! Running every day against somebody's inputs and checking the answers
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::input::Inputs;
+use crate::manifest::Manifest;
+use crate::solutions::{Answer, Day};
+
+/// How one part of a day went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The answer was the one expected
+    Pass,
+
+    /// The answer wasn't the one expected
+    Fail,
+
+    /// There was an answer, but nothing to check it against
+    Unchecked,
+
+    /// There was no input to run on
+    NoInput,
+
+    /// The input didn't make sense
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Unchecked => "unchecked",
+            Status::NoInput => "no input",
+            Status::Error => "ERROR",
+        };
+
+        f.pad(status)
+    }
+}
+
+/// The result of running one part of a day for a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub profile: String,
+    pub day: u8,
+    pub part: u8,
+    pub expected: Option<Answer>,
+
+    /// The answer, or why there wasn't one, if there was an input
+    pub actual: Option<Result<Answer, String>>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (None, _) => Status::NoInput,
+            (Some(Err(_)), _) => Status::Error,
+            (Some(Ok(_)), None) => Status::Unchecked,
+            (Some(Ok(actual)), Some(expected)) if actual == expected => Status::Pass,
+            (Some(Ok(_)), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+/// Run both parts of the given days on a profile's inputs, and compare
+/// them with the answers in the manifest
+pub fn verify(inputs: &Inputs, manifest: &Manifest, days: &[Day]) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for day in days {
+        let input = inputs.load(day.day).ok();
+        for (part, solver) in day.parts() {
+            let start = Instant::now();
+            let actual = input.as_deref().map(|input| solver(input).map_err(|err| err.to_string()));
+            let elapsed = start.elapsed();
+
+            outcomes.push(Outcome {
+                profile: inputs.profile().to_string(),
+                day: day.day,
+                part,
+                expected: manifest.expected(inputs.profile(), day.day, part).cloned(),
+                actual,
+                elapsed,
+            });
+        }
+    }
+
+    outcomes
+}
+
+/// Run both parts of the given days on each of the named profiles'
+/// inputs. The input directory only ever holds one set of inputs, so
+/// it's left out, and each profile's inputs come from its own profile
+/// directory
+pub fn verify_named(inputs: &Inputs, profiles: &[&str], manifest: &Manifest, days: &[Day]) -> Report {
+    let outcomes = profiles
+        .iter()
+        .flat_map(|&profile| verify(&inputs.clone().with_profile(profile).without_input_dir(), manifest, days))
+        .collect();
+
+    Report(outcomes)
+}
+
+/// A table of outcomes
+///
+/// ```
+/// # use aoc2015::input::Inputs;
+/// # use aoc2015::manifest::Manifest;
+/// # use aoc2015::solutions::DAYS;
+/// # use aoc2015::verify::*;
+/// let manifest = Manifest::parse("[default]\nday1.part1 = 74\nday1.part2 = 1").unwrap();
+/// let report = Report(verify(&Inputs::bundled_only(), &manifest, &DAYS[..1]));
+///
+/// assert_eq!(report.count(Status::Pass), 1);
+/// assert_eq!(report.count(Status::Fail), 1);
+/// assert!(!report.passed());
+/// assert!(report.to_string().ends_with("1 passed, 1 failed, 0 errors, 0 unchecked, 0 without input"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report(pub Vec<Outcome>);
+
+impl Report {
+    pub fn count(&self, status: Status) -> usize {
+        self.0.iter().filter(|outcome| outcome.status() == status).count()
+    }
+
+    /// Whether nothing failed. Answers that couldn't be checked don't
+    /// count against it
+    pub fn passed(&self) -> bool {
+        self.count(Status::Fail) == 0 && self.count(Status::Error) == 0
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |answer: Option<&Answer>| answer.map(Answer::to_string).unwrap_or_else(|| "-".to_string());
+
+        writeln!(f, "{:<10} {:>3} {:>5}  {:<10}  {:<10}  {:>10}  result", "profile", "day", "part", "expected", "actual", "time")?;
+        for outcome in &self.0 {
+            writeln!(
+                f,
+                "{:<10} {:>3} {:>5}  {:<10}  {:<10}  {:>10}  {}",
+                outcome.profile,
+                outcome.day,
+                outcome.part,
+                show(outcome.expected.as_ref()),
+                match &outcome.actual {
+                    Some(Err(err)) => err.clone(),
+                    actual => show(actual.as_ref().and_then(|actual| actual.as_ref().ok())),
+                },
+                match outcome.actual {
+                    Some(_) => format!("{:.2?}", outcome.elapsed),
+                    None => "-".to_string(),
+                },
+                outcome.status(),
+            )?;
+        }
+
+        write!(
+            f,
+            "{} passed, {} failed, {} errors, {} unchecked, {} without input",
+            self.count(Status::Pass),
+            self.count(Status::Fail),
+            self.count(Status::Error),
+            self.count(Status::Unchecked),
+            self.count(Status::NoInput),
+        )
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+    use crate::solutions::DAYS;
+
+    #[test]
+    pub fn missing_inputs_and_answers() {
+        let manifest = Manifest::parse("[elf]\nday2.part1 = 58").unwrap();
+        let outcomes = verify(&Inputs::bundled_only().with_profile("elf"), &manifest, &DAYS[1..2]);
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|outcome| outcome.status() == Status::NoInput));
+        assert_eq!(outcomes[0].expected, Some(Answer::from(58)));
+
+        let outcomes = verify(&Inputs::bundled_only(), &manifest, &DAYS[1..2]);
+        assert!(outcomes.iter().all(|outcome| outcome.status() == Status::Unchecked));
+    }
+
+    #[test]
+    pub fn bad_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc2015-verify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day1.txt"), "(()x").unwrap();
+
+        let report = Report(verify(&Inputs::bundled_only().with_input_dir(&dir), &Manifest::default(), &DAYS[..1]));
+        assert_eq!(report.count(Status::Error), 2);
+        assert!(!report.passed());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn profiles_use_their_own_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc2015-profiles-{}", std::process::id()));
+        for (profile, input) in [("elf", "((("), ("gnome", ")))"), ("shared", "()")] {
+            std::fs::create_dir_all(dir.join("aoc2015").join(profile)).unwrap();
+            std::fs::write(dir.join("aoc2015").join(profile).join("day1.txt"), input).unwrap();
+        }
+
+        // The input directory would give everybody the same input
+        let inputs = Inputs::bundled_only().with_config_dir(&dir).with_input_dir(dir.join("aoc2015").join("shared"));
+        let manifest = Manifest::parse("[elf]\nday1.part1 = 3\n[gnome]\nday1.part1 = -3\nday1.part2 = 1").unwrap();
+        let report = verify_named(&inputs, &["elf", "gnome"], &manifest, &DAYS[..1]);
+
+        let statuses = report.0.iter().map(|outcome| (outcome.profile.as_str(), outcome.part, outcome.status())).collect::<Vec<_>>();
+        assert_eq!(statuses, [
+            ("elf", 1, Status::Pass),
+            ("elf", 2, Status::Error),
+            ("gnome", 1, Status::Pass),
+            ("gnome", 2, Status::Pass),
+        ]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn bundled_manifest() {
+        let manifest = Manifest::parse(include_str!("../answers.toml")).unwrap();
+        for day in DAYS {
+            for part in [1, 2] {
+                assert!(manifest.expected("default", day.day, part).is_some(), "Day {} part {part}", day.day);
+            }
+        }
+    }
+}
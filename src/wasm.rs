@@ -0,0 +1,101 @@
+//! WebAssembly bindings, for running the solvers in a browser
+//!
+//! Every function takes the puzzle input as a string and returns a JSON
+//! object with the answers, or throws an `Error` saying what was wrong
+//! with the input. The README has the commands for building them.
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::day1;
+use crate::day5;
+use crate::day7::{Circuit, CircuitError};
+use crate::solutions::{self, Answer};
+
+/// Day 1: `{"floor": ..., "basement": ...}`, where `basement` is `null`
+/// if Santa never gets there
+#[wasm_bindgen]
+pub fn day1(input: &str) -> Result<String, JsError> {
+    let floor = day1::find_floor(input).map_err(error)?;
+    let basement = match day1::find_basement(input) {
+        Ok(position) => Answer::from(position).to_json(),
+        Err(day1::Error::NoBasement) => "null".to_string(),
+        Err(err) => return Err(error(err)),
+    };
+
+    Ok(object(&[("floor", Answer::from(floor).to_json()), ("basement", basement)]))
+}
+
+/// Day 2: `{"paper": ..., "ribbon": ...}`, the totals for every present
+#[wasm_bindgen]
+pub fn day2(input: &str) -> Result<String, JsError> {
+    parts(2, input, ["paper", "ribbon"])
+}
+
+/// Day 3: `{"santa": ..., "with_robo_santa": ...}`, how many houses get
+/// at least one present
+#[wasm_bindgen]
+pub fn day3(input: &str) -> Result<String, JsError> {
+    parts(3, input, ["santa", "with_robo_santa"])
+}
+
+/// Day 5: `{"nice_v1": ..., "nice_v2": ..., "strings": [...]}`, with
+/// each line classified as `{"string": ..., "v1": ..., "v2": ...}`
+#[wasm_bindgen]
+pub fn day5(input: &str) -> String {
+    let mut counts = [0usize; 2];
+    let strings = input
+        .lines()
+        .map(|line| {
+            let (v1, v2) = day5::scan(line);
+            counts[0] += usize::from(v1);
+            counts[1] += usize::from(v2);
+            object(&[("string", Answer::from(line).to_json()), ("v1", v1.to_string()), ("v2", v2.to_string())])
+        })
+        .collect::<Vec<_>>();
+
+    object(&[
+        ("nice_v1", Answer::from(counts[0]).to_json()),
+        ("nice_v2", Answer::from(counts[1]).to_json()),
+        ("strings", format!("[{}]", strings.join(","))),
+    ])
+}
+
+/// Day 6: `{"lit": ..., "brightness": ...}` once every instruction has
+/// been followed
+#[wasm_bindgen]
+pub fn day6(input: &str) -> Result<String, JsError> {
+    parts(6, input, ["lit", "brightness"])
+}
+
+/// Day 7: `{"wire": ..., "signal": ...}`, the signal that ends up on a
+/// wire
+#[wasm_bindgen]
+pub fn day7(input: &str, wire: &str) -> Result<String, JsError> {
+    let lines = input.lines().map(str::trim).collect::<Vec<_>>();
+    let mut circuit = Circuit::load(&lines).map_err(error)?;
+    if !circuit.drives(wire) {
+        return Err(error(CircuitError::Undriven(wire.to_string())));
+    }
+
+    let signal = circuit.evaluate(wire);
+    Ok(object(&[("wire", Answer::from(wire).to_json()), ("signal", Answer::from(signal).to_json())]))
+}
+
+/// Both parts of a registered day, under the given names
+fn parts(day: u8, input: &str, names: [&str; 2]) -> Result<String, JsError> {
+    let day = solutions::day(day).expect("Every bound day has a solution");
+    let [first, second] = [day.part1, day.part2].map(|solver| solver(input).map(|answer| answer.to_json()));
+    Ok(object(&[(names[0], first.map_err(error)?), (names[1], second.map_err(error)?)]))
+}
+
+/// A JSON object from keys and values that are already JSON
+fn object(fields: &[(&str, String)]) -> String {
+    let fields = fields.iter().map(|(key, value)| format!("{}:{value}", Answer::from(*key).to_json())).collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn error(err: impl fmt::Display) -> JsError {
+    JsError::new(&err.to_string())
+}
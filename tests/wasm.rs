@@ -0,0 +1,49 @@
+//! The WebAssembly bindings, run headlessly under Node with
+//! `wasm-pack test --node -- --features wasm`
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use aoc2015::input::bundled;
+use aoc2015::wasm;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn day1() {
+    assert_eq!(wasm::day1(bundled(1).unwrap()).unwrap(), r#"{"floor":74,"basement":1795}"#);
+    assert_eq!(wasm::day1("((").unwrap(), r#"{"floor":2,"basement":null}"#);
+    assert!(wasm::day1("(x").is_err());
+}
+
+#[wasm_bindgen_test]
+fn day2() {
+    assert_eq!(wasm::day2(bundled(2).unwrap()).unwrap(), r#"{"paper":1598415,"ribbon":3812909}"#);
+    assert!(wasm::day2("1x2").is_err());
+}
+
+#[wasm_bindgen_test]
+fn day3() {
+    assert_eq!(wasm::day3(bundled(3).unwrap()).unwrap(), r#"{"santa":2572,"with_robo_santa":2631}"#);
+    assert!(wasm::day3("^>v<x").is_err());
+}
+
+#[wasm_bindgen_test]
+fn day5() {
+    assert_eq!(
+        wasm::day5("ugknbfddgicrmopn\nqjhvhtzxzqqjkmpb"),
+        r#"{"nice_v1":1,"nice_v2":1,"strings":[{"string":"ugknbfddgicrmopn","v1":true,"v2":false},{"string":"qjhvhtzxzqqjkmpb","v1":false,"v2":true}]}"#
+    );
+    assert!(wasm::day5(bundled(5).unwrap()).starts_with(r#"{"nice_v1":255,"nice_v2":55,"#));
+}
+
+#[wasm_bindgen_test]
+fn day6() {
+    assert_eq!(wasm::day6("turn on 0,0 through 9,9\ntoggle 0,0 through 0,9").unwrap(), r#"{"lit":90,"brightness":120}"#);
+    assert!(wasm::day6("turn sideways 0,0 through 1,1").is_err());
+}
+
+#[wasm_bindgen_test]
+fn day7() {
+    assert_eq!(wasm::day7(bundled(7).unwrap(), "a").unwrap(), r#"{"wire":"a","signal":16076}"#);
+    assert_eq!(wasm::day7("123 -> x\nNOT x -> h", "h").unwrap(), r#"{"wire":"h","signal":65412}"#);
+    assert!(wasm::day7("123 -> x", "y").is_err());
+}